@@ -1,154 +1,671 @@
+use crate::climod::ArchiveFormat;
+use crate::errors;
+use crate::output::{info, verbose};
+use crate::project;
+use crate::utils;
 use anyhow::{Context, Result, anyhow};
-use chrono::Local;
+use chrono::{DateTime, Local, NaiveDateTime};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde_json::{Value, json};
 use std::fs;
 use std::fs::File;
-use std::io::{self, Read, Write};
-use std::path::PathBuf;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 use zip::ZipArchive;
 
-/// Return the archives directory (`~/.proj/archives`)
-pub fn get_archives_dir() -> PathBuf {
-    dirs::home_dir().unwrap().join(".proj/archives")
+/// Return the archives directory (`~/.proj/archives`, or
+/// `$PROJECT_HOME/.archives` when that is set)
+pub fn get_archives_dir() -> Result<PathBuf> {
+    if let Some(home) = project::project_home() {
+        return Ok(home.join(".archives"));
+    }
+    Ok(utils::home_dir()?.join(".proj/archives"))
 }
 
-pub fn archive_project(project_name: &str) -> Result<()> {
-    let projects_dir = dirs::home_dir()
-        .ok_or_else(|| anyhow!("Could not locate home directory"))?
-        .join(".proj/projects");
+/// Locate a project directory for archiving or exporting by name
+///
+/// The path is canonicalized so a project registered through a ~/projects
+/// symlink resolves to its real directory rather than the link.
+fn find_project_dir(project_name: &str) -> Result<PathBuf> {
+    let projects_dir = utils::home_dir()?.join(".proj/projects");
     let project_dir = projects_dir.join(project_name);
 
     // 🧩 If project.json doesn’t exist here, check ~/projects/<name>
-    let real_path = if project_dir.exists() {
+    let found = if project_dir.exists() {
         project_dir
     } else {
-        let alt_path = dirs::home_dir()
-            .ok_or_else(|| anyhow!("Could not locate home directory"))?
-            .join("projects")
-            .join(project_name);
+        let alt_path = project::projects_dir()?.join(project_name);
 
         if alt_path.exists() {
             alt_path
+        } else if let Some(found) = project::find_project_path(project_name) {
+            found
         } else {
             // Fallback: maybe it’s in current working directory
             let cwd_path = std::env::current_dir()?.join(project_name);
             if cwd_path.exists() {
                 cwd_path
             } else {
-                return Err(anyhow!("Project '{}' not found", project_name));
+                return Err(errors::not_found(format!(
+                    "Project '{}' not found",
+                    project_name
+                )));
             }
         }
     };
 
-    // 📦 Prepare archive directory
-    let archive_dir = get_archives_dir();
-    fs::create_dir_all(&archive_dir)?;
-
-    let timestamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
-    let archive_path = archive_dir.join(format!("{}_{}.zip", project_name, timestamp));
-
-    let zip_file = std::fs::File::create(&archive_path)
-        .with_context(|| format!("Could not create archive file: {}", archive_path.display()))?;
+    fs::canonicalize(&found).with_context(|| format!("Failed to resolve {}", found.display()))
+}
 
-    let mut zip = zip::ZipWriter::new(zip_file);
-    let options: zip::write::FileOptions<'_, ()> =
-        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+pub fn archive_project(
+    project_name: &str,
+    keep: bool,
+    format: ArchiveFormat,
+    dry_run: bool,
+) -> Result<()> {
+    let real_path = find_project_dir(project_name)?;
+    archive_dir(project_name, &real_path, keep, format, dry_run).map(|_| ())
+}
 
-    // 🧾 Recursively add files
-    for entry in walkdir::WalkDir::new(&real_path) {
-        let entry = entry?;
-        let path = entry.path();
+/// Archive the project at `real_path` under `project_name`, returning the
+/// size of the archive written (0 on a dry run)
+fn archive_dir(
+    project_name: &str,
+    real_path: &Path,
+    keep: bool,
+    format: ArchiveFormat,
+    dry_run: bool,
+) -> Result<u64> {
+    // 📦 Prepare archive directory
+    let archive_dir = get_archives_dir()?;
+    let timestamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
+    let archive_path = archive_dir.join(format!(
+        "{}_{}.{}",
+        project_name,
+        timestamp,
+        format.extension()
+    ));
 
-        if path.is_file() {
-            let name_in_zip = path.strip_prefix(&real_path).unwrap().to_str().unwrap();
-            zip.start_file(name_in_zip, options)?;
-            let mut f = std::fs::File::open(path)?;
-            let mut buffer = Vec::new();
-            f.read_to_end(&mut buffer)?;
-            zip.write_all(&buffer)?;
+    if dry_run {
+        println!(
+            "Would archive {} files from '{}' to '{}'",
+            archive_files(real_path)?.len(),
+            real_path.display(),
+            archive_path.display()
+        );
+        if !keep {
+            println!("Would delete '{}'", real_path.display());
+            let projects_link = project::projects_dir()?.join(project_name);
+            if projects_link.is_symlink() {
+                println!("Would remove link '{}'", projects_link.display());
+            }
         }
+        return Ok(0);
     }
 
-    zip.finish()?;
+    fs::create_dir_all(&archive_dir)?;
 
-    println!(
+    // 🔍 The archive is verified before anything is deleted
+    write_archive(real_path, &archive_path, format).with_context(|| {
+        format!(
+            "Archiving failed, project '{}' was left untouched",
+            project_name
+        )
+    })?;
+
+    info!(
         "📦 Archived project '{}' to {}",
         project_name,
         archive_path.display()
     );
+    let size = fs::metadata(&archive_path)?.len();
+
+    if keep {
+        info!("📁 Kept original project at {}", real_path.display());
+        return Ok(size);
+    }
 
     // 🗑️ Remove project directory and symlink after archiving
     if real_path.exists() {
-        std::fs::remove_dir_all(&real_path)
+        std::fs::remove_dir_all(real_path)
             .with_context(|| format!("Failed to delete {}", real_path.display()))?;
     }
 
-    let projects_link = dirs::home_dir()
-        .unwrap()
-        .join("projects")
-        .join(project_name);
-    if projects_link.exists() {
+    let projects_link = project::projects_dir()?.join(project_name);
+    // The link is dangling by now, so don't follow it
+    if projects_link.is_symlink() {
         std::fs::remove_file(&projects_link).ok();
     }
 
-    Ok(())
+    Ok(size)
 }
 
-pub fn list_archives() -> Result<()> {
-    let archives_dir = get_archives_dir();
+/// Archive every project whose status is `status`, carrying on past failures
+pub fn archive_all(status: &str, keep: bool, format: ArchiveFormat, dry_run: bool) -> Result<()> {
+    let mut matching = Vec::new();
+    for path in project::collect_projects() {
+        match project::read_project_json(&path.join(".proj/project.json")) {
+            Ok(data)
+                if data
+                    .get("status")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("active")
+                    == status =>
+            {
+                matching.push(path)
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("❌ {:#}", e),
+        }
+    }
 
-    if !archives_dir.exists() {
-        println!("No archives found.");
+    if matching.is_empty() {
+        info!("No projects with status '{}'", status);
         return Ok(());
     }
 
-    let entries = fs::read_dir(&archives_dir)?;
-    let mut found_any = false;
+    let (mut archived, mut failed, mut total) = (0, 0, 0);
+    for path in &matching {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        match archive_dir(&name, path, keep, format, dry_run) {
+            Ok(size) => {
+                archived += 1;
+                total += size;
+            }
+            Err(e) => {
+                eprintln!("❌ {}: {:#}", name, e);
+                failed += 1;
+            }
+        }
+    }
+
+    if dry_run {
+        println!("Would archive {} projects", archived);
+    } else {
+        info!(
+            "📦 Archived {} projects ({})",
+            archived,
+            utils::human_size(total)
+        );
+    }
+
+    if failed > 0 {
+        return Err(anyhow!(
+            "Failed to archive {} of {} projects",
+            failed,
+            matching.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Directories that are never archived
+const ARCHIVE_SKIP_DIRS: &[&str] = &[".git", "target"];
+
+/// Read the globs in `.proj/archiveignore`, skipping blank lines and `#` comments
+fn archive_ignore_patterns(project_path: &Path) -> Vec<String> {
+    fs::read_to_string(project_path.join(".proj/archiveignore"))
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| l.trim_end_matches('/').to_string())
+        .collect()
+}
 
-    for entry in entries {
+/// Whether `relative` (a path inside the project) matches an archiveignore
+/// pattern. Patterns containing `/` match the whole relative path, others
+/// match any single path component
+fn is_archive_ignored(relative: &Path, patterns: &[String]) -> bool {
+    let relative_str = relative.to_string_lossy();
+    patterns.iter().any(|pattern| {
+        if pattern.contains('/') {
+            utils::glob_match(pattern.trim_start_matches('/'), &relative_str)
+        } else {
+            relative
+                .components()
+                .any(|c| utils::glob_match(pattern, &c.as_os_str().to_string_lossy()))
+        }
+    })
+}
+
+/// List the files of a project that go into its archive
+///
+/// `.git` and `target` directories are always skipped. Globs listed in
+/// `.proj/archiveignore` (one per line) add to those built-in excludes,
+/// they never bring excluded directories back.
+fn archive_files(project_path: &Path) -> Result<Vec<PathBuf>> {
+    let patterns = archive_ignore_patterns(project_path);
+    let mut files = Vec::new();
+
+    let walker = walkdir::WalkDir::new(project_path)
+        .into_iter()
+        .filter_entry(|entry| {
+            if entry.depth() == 0 {
+                return true;
+            }
+            let is_skipped_dir = entry.file_type().is_dir()
+                && entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|n| ARCHIVE_SKIP_DIRS.contains(&n));
+            let relative = entry
+                .path()
+                .strip_prefix(project_path)
+                .unwrap_or(entry.path());
+            !is_skipped_dir && !is_archive_ignored(relative, &patterns)
+        });
+
+    for entry in walker {
         let entry = entry?;
-        if entry
-            .path()
-            .extension()
-            .map(|e| e == "zip")
-            .unwrap_or(false)
-        {
+        if entry.path().is_file() {
+            files.push(entry.into_path());
+        }
+    }
+
+    Ok(files)
+}
+
+/// Write the archivable files of `src` to `dest` and verify the result,
+/// returning the number of files written
+pub fn write_archive(src: &Path, dest: &Path, format: ArchiveFormat) -> Result<usize> {
+    let files = archive_files(src)?;
+    let file = File::create(dest)
+        .with_context(|| format!("Could not create archive file: {}", dest.display()))?;
+
+    match format {
+        ArchiveFormat::Zip => write_zip(file, src, &files)?,
+        ArchiveFormat::Tgz => write_tgz(file, src, &files)?,
+    }
+
+    verify_archive(dest, format, files.len())
+        .with_context(|| format!("Archive verification failed for {}", dest.display()))?;
+    Ok(files.len())
+}
+
+/// Export a project to an arbitrary archive path, leaving it in place
+///
+/// The format follows the extension: `.tar.gz`/`.tgz` for a tarball,
+/// anything else for a zip.
+pub fn export_project(project_name: &str, dest: &Path, dry_run: bool) -> Result<()> {
+    let real_path = find_project_dir(project_name)?;
+    let dest_name = dest.to_string_lossy();
+    let format = if dest_name.ends_with(".tar.gz") || dest_name.ends_with(".tgz") {
+        ArchiveFormat::Tgz
+    } else {
+        ArchiveFormat::Zip
+    };
+
+    if dest.exists() {
+        return Err(errors::conflict(format!(
+            "'{}' already exists",
+            dest.display()
+        )));
+    }
+
+    if dry_run {
+        println!(
+            "Would export {} files from '{}' to '{}'",
+            archive_files(&real_path)?.len(),
+            real_path.display(),
+            dest.display()
+        );
+        return Ok(());
+    }
+
+    let file_count = write_archive(&real_path, dest, format)?;
+    info!(
+        "📦 Exported project '{}' ({} files) to {}",
+        project_name,
+        file_count,
+        dest.display()
+    );
+    Ok(())
+}
+
+fn write_zip(file: File, root: &Path, files: &[PathBuf]) -> Result<()> {
+    let mut zip = zip::ZipWriter::new(file);
+    let options: zip::write::FileOptions<'_, ()> =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    for path in files {
+        let name_in_zip = path.strip_prefix(root).unwrap().to_str().unwrap();
+        verbose!("  adding {}", name_in_zip);
+        zip.start_file(name_in_zip, options)?;
+        let mut f = File::open(path)?;
+        io::copy(&mut f, &mut zip)?;
+    }
+
+    zip.finish()?.sync_all()?;
+    Ok(())
+}
+
+/// Write a gzipped tarball, keeping Unix permissions and mtimes
+fn write_tgz(file: File, root: &Path, files: &[PathBuf]) -> Result<()> {
+    let encoder = GzEncoder::new(file, Compression::default());
+    let mut tar = tar::Builder::new(encoder);
+
+    for path in files {
+        let name_in_tar = path.strip_prefix(root).unwrap();
+        verbose!("  adding {}", name_in_tar.display());
+        tar.append_path_with_name(path, name_in_tar)?;
+    }
+
+    tar.into_inner()?.finish()?.sync_all()?;
+    Ok(())
+}
+
+/// Re-open a freshly written archive and check it holds `expected` entries
+fn verify_archive(archive_path: &Path, format: ArchiveFormat, expected: usize) -> Result<()> {
+    let file = File::open(archive_path)?;
+    let read_error = || format!("Could not read archive {}", archive_path.display());
+
+    let entries = match format {
+        ArchiveFormat::Zip => ZipArchive::new(file).with_context(read_error)?.len(),
+        ArchiveFormat::Tgz => {
+            let mut tar = tar::Archive::new(GzDecoder::new(file));
+            let mut count = 0;
+            for entry in tar.entries().with_context(read_error)? {
+                entry.with_context(read_error)?;
+                count += 1;
+            }
+            count
+        }
+    };
+
+    if entries != expected {
+        return Err(anyhow!(
+            "Archive {} contains {} entries, expected {}",
+            archive_path.display(),
+            entries,
+            expected
+        ));
+    }
+
+    Ok(())
+}
+
+/// Split an archive file name into its stem and format, e.g.
+/// `app_20240101_120000.tar.gz` into `app_20240101_120000` and tgz
+fn split_archive_file(file_name: &str) -> Option<(&str, ArchiveFormat)> {
+    [ArchiveFormat::Zip, ArchiveFormat::Tgz]
+        .into_iter()
+        .find_map(|format| {
+            file_name
+                .strip_suffix(format.extension())
+                .and_then(|rest| rest.strip_suffix('.'))
+                .map(|stem| (stem, format))
+        })
+}
+
+/// Locate an archive by name, with or without its extension
+fn find_archive_file(archive_name: &str) -> Result<(String, PathBuf, ArchiveFormat)> {
+    let archives_dir = get_archives_dir()?;
+
+    if let Some((stem, format)) = split_archive_file(archive_name) {
+        let path = archives_dir.join(archive_name);
+        if path.exists() {
+            return Ok((stem.to_string(), path, format));
+        }
+    }
+
+    [ArchiveFormat::Zip, ArchiveFormat::Tgz]
+        .into_iter()
+        .map(|format| {
+            let path = archives_dir.join(format!("{}.{}", archive_name, format.extension()));
+            (archive_name.to_string(), path, format)
+        })
+        .find(|(_, path, _)| path.exists())
+        .ok_or_else(|| errors::not_found(format!("Archive '{}' not found", archive_name)))
+}
+
+/// An archive file found in the archives directory
+struct ArchiveEntry {
+    name: String,
+    project: String,
+    format: ArchiveFormat,
+    size: u64,
+    modified: DateTime<Local>,
+}
+
+/// All archives in the archives directory, newest first
+fn archive_entries() -> Result<Vec<ArchiveEntry>> {
+    let mut archives = Vec::new();
+
+    if let Ok(entries) = fs::read_dir(get_archives_dir()?) {
+        for entry in entries {
+            let entry = entry?;
             let file_name = entry.file_name().into_string().unwrap_or_default();
-            println!("📦 {}", file_name.trim_end_matches(".zip"));
-            found_any = true;
+            let Some((stem, format)) = split_archive_file(&file_name) else {
+                continue;
+            };
+            let metadata = entry.metadata()?;
+
+            archives.push(ArchiveEntry {
+                name: stem.to_string(),
+                project: split_archive_name(stem)
+                    .map(|(name, _)| name)
+                    .unwrap_or(stem)
+                    .to_string(),
+                format,
+                size: metadata.len(),
+                modified: metadata.modified()?.into(),
+            });
         }
     }
 
-    if !found_any {
+    archives.sort_by_key(|a| std::cmp::Reverse(a.modified));
+    Ok(archives)
+}
+
+pub fn list_archives(as_json: bool) -> Result<()> {
+    let archives = archive_entries()?;
+
+    if as_json {
+        let list: Vec<Value> = archives
+            .iter()
+            .map(|a| {
+                json!({
+                    "name": a.name,
+                    "project": a.project,
+                    "format": a.format.extension(),
+                    "size": a.size,
+                    "modified": a.modified.to_rfc3339(),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&list)?);
+        return Ok(());
+    }
+
+    if archives.is_empty() {
         println!("No archives found.");
+        return Ok(());
+    }
+
+    // Group by project, ordered by each project's newest archive
+    let mut groups: Vec<(&str, Vec<&ArchiveEntry>)> = Vec::new();
+    for archive in &archives {
+        match groups.iter_mut().find(|(name, _)| *name == archive.project) {
+            Some((_, list)) => list.push(archive),
+            None => groups.push((&archive.project, vec![archive])),
+        }
+    }
+
+    for (project, list) in groups {
+        println!("📦 {}", project);
+        let width = list.iter().map(|a| a.name.len()).max().unwrap_or(0);
+        for archive in list {
+            let suffix = match archive.format {
+                ArchiveFormat::Zip => "",
+                ArchiveFormat::Tgz => "  (tar.gz)",
+            };
+            println!(
+                "   {:<width$}  {:>9}  {}{}",
+                archive.name,
+                utils::human_size(archive.size),
+                archive.modified.format("%Y-%m-%d %H:%M"),
+                suffix,
+                width = width
+            );
+        }
     }
 
     Ok(())
 }
 
-pub fn remove_archive(name: &str) -> Result<()> {
-    let archives_dir = get_archives_dir();
-    let archive_path = archives_dir.join(format!("{}.zip", name));
+/// Delete all but the `keep` newest archives of every project, ordered by
+/// the timestamp in their file names
+pub fn prune_archives(keep: usize, dry_run: bool) -> Result<()> {
+    let mut groups: Vec<(String, Vec<(String, ArchiveEntry)>)> = Vec::new();
+    for archive in archive_entries()? {
+        // Archives without a parseable timestamp are never pruned
+        let Some((_, timestamp)) = split_archive_name(&archive.name) else {
+            continue;
+        };
+        let timestamp = timestamp.to_string();
+        match groups.iter_mut().find(|(name, _)| *name == archive.project) {
+            Some((_, list)) => list.push((timestamp, archive)),
+            None => groups.push((archive.project.clone(), vec![(timestamp, archive)])),
+        }
+    }
+
+    let mut removed = 0;
+    for (_, mut list) in groups {
+        list.sort_by(|a, b| b.0.cmp(&a.0));
+        for (_, archive) in list.into_iter().skip(keep) {
+            let path = get_archives_dir()?.join(format!(
+                "{}.{}",
+                archive.name,
+                archive.format.extension()
+            ));
+            if dry_run {
+                println!("Would remove archive '{}'", archive.name);
+            } else {
+                fs::remove_file(&path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))?;
+                info!("🗑️  Removed archive '{}'", archive.name);
+            }
+            removed += 1;
+        }
+    }
 
-    if !archive_path.exists() {
-        return Err(anyhow!("Archive '{}' not found", name));
+    if removed == 0 {
+        println!("Nothing to prune.");
+    } else if !dry_run {
+        info!(
+            "✅ Pruned {} archive(s), kept up to {} per project",
+            removed, keep
+        );
     }
 
+    Ok(())
+}
+
+pub fn remove_archive(name: &str) -> Result<()> {
+    let (name, archive_path, _) = find_archive_file(name)?;
+
     fs::remove_file(&archive_path)?;
-    println!("🗑️  Removed archive '{}'", name);
+    info!("🗑️  Removed archive '{}'", name);
     Ok(())
 }
 
-pub fn restore_archive(archive_name: &str, destination: Option<&str>) -> Result<()> {
-    let archives_dir = get_archives_dir();
-    let archive_path = archives_dir.join(format!("{}.zip", archive_name));
+/// Split an archive name like `my_app_20240101_120000` into the project
+/// name and its timestamp, validating the trailing `YYYYMMDD_HHMMSS` part
+pub fn split_archive_name(archive_name: &str) -> Option<(&str, &str)> {
+    let (rest, time) = archive_name.rsplit_once('_')?;
+    let (name, date) = rest.rsplit_once('_')?;
+    if name.is_empty() {
+        return None;
+    }
+
+    let timestamp = &archive_name[name.len() + 1..];
+    NaiveDateTime::parse_from_str(&format!("{}_{}", date, time), "%Y%m%d_%H%M%S").ok()?;
+    Some((name, timestamp))
+}
 
-    if !archive_path.exists() {
-        return Err(anyhow!("Archive '{}' not found", archive_name));
+/// Find the newest archive for `project_name` in the archives directory
+pub fn find_latest_archive(project_name: &str) -> Result<String> {
+    let archives_dir = get_archives_dir()?;
+    let mut latest: Option<(String, String)> = None;
+
+    if let Ok(entries) = fs::read_dir(&archives_dir) {
+        for entry in entries.flatten() {
+            let file_name = entry.file_name().into_string().unwrap_or_default();
+            let Some((stem, _)) = split_archive_file(&file_name) else {
+                continue;
+            };
+
+            if let Some((name, timestamp)) = split_archive_name(stem)
+                && name == project_name
+                && latest.as_ref().is_none_or(|(_, t)| timestamp > t.as_str())
+            {
+                latest = Some((stem.to_string(), timestamp.to_string()));
+            }
+        }
     }
 
+    latest.map(|(stem, _)| stem).ok_or_else(|| {
+        errors::not_found(format!("No archives found for project '{}'", project_name))
+    })
+}
+
+/// Resolve where a zip entry is extracted, creating its parent directories
+///
+/// Entries whose name is absolute or climbs out with `..` are rejected, as is
+/// anything that resolves outside `root` through a symlinked directory
+/// (zip-slip).
+fn contained_output_path(
+    root: &Path,
+    entry_name: &str,
+    enclosed_name: Option<PathBuf>,
+    is_dir: bool,
+) -> Result<PathBuf> {
+    let escapes = || {
+        anyhow!(
+            "Archive entry '{}' would be extracted outside '{}'",
+            entry_name,
+            root.display()
+        )
+    };
+
+    let outpath = root.join(enclosed_name.ok_or_else(escapes)?);
+    let dir = if is_dir {
+        outpath.as_path()
+    } else {
+        outpath.parent().unwrap_or(root)
+    };
+    // Check the deepest existing ancestor before creating anything below it
+    let existing = dir.ancestors().find(|a| a.exists()).unwrap_or(root);
+    if !fs::canonicalize(existing)?.starts_with(root) {
+        return Err(escapes());
+    }
+    fs::create_dir_all(dir)?;
+    if outpath.is_symlink() && !fs::canonicalize(&outpath).is_ok_and(|p| p.starts_with(root)) {
+        return Err(escapes());
+    }
+
+    Ok(outpath)
+}
+
+/// Restore an archive into `destination` (default `~/projects`)
+///
+/// An existing destination is replaced after confirmation, or straight away
+/// with `force`; with `merge` the archive is extracted into it instead.
+pub fn restore_archive(
+    archive_name: &str,
+    destination: Option<&str>,
+    force: bool,
+    merge: bool,
+) -> Result<()> {
+    let (archive_name, archive_path, format) = find_archive_file(archive_name)?;
+    let archive_name = archive_name.as_str();
+
     // Extract original project name from archive
     // This assumes archives are named like "projectname_YYYYMMDD_HHMMSS.zip"
-    let original_name = archive_name.splitn(2, '_').next().ok_or_else(|| {
+    let (original_name, _) = split_archive_name(archive_name).ok_or_else(|| {
         anyhow!(
             "Failed to parse original project name from '{}'",
             archive_name
@@ -159,56 +676,98 @@ pub fn restore_archive(archive_name: &str, destination: Option<&str>) -> Result<
     let dest_path = if let Some(dest) = destination {
         PathBuf::from(dest).join(original_name)
     } else {
-        dirs::home_dir()
-            .ok_or_else(|| anyhow!("Failed to locate home directory"))?
-            .join("projects")
-            .join(original_name)
+        project::projects_dir()?.join(original_name)
     };
 
-    if dest_path.exists() {
-        return Err(anyhow!(
-            "Destination folder '{}' already exists",
-            dest_path.display()
-        ));
+    // A ~/projects symlink stands for the project it points to, which is
+    // replaced in place so the link stays valid; a dangling one is dropped
+    let dest_path = if dest_path.is_symlink() {
+        match fs::canonicalize(&dest_path) {
+            Ok(real_path) => real_path,
+            Err(_) => {
+                fs::remove_file(&dest_path)?;
+                dest_path
+            }
+        }
+    } else {
+        dest_path
+    };
+
+    if dest_path.exists() && !merge {
+        if !force {
+            print!(
+                "⚠️  '{}' already exists. Replace it with the archive? [y/N]: ",
+                dest_path.display()
+            );
+            io::stdout().flush()?;
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            let answer = input.trim().to_lowercase();
+            if answer != "y" && answer != "yes" {
+                println!("❎ Aborted restore of '{}'", archive_name);
+                return Ok(());
+            }
+        }
+
+        fs::remove_dir_all(&dest_path)
+            .with_context(|| format!("Failed to remove {}", dest_path.display()))?;
+        info!("🗑️  Removed existing '{}'", dest_path.display());
     }
 
     fs::create_dir_all(&dest_path)?;
     let file = File::open(&archive_path)?;
-    let mut zip = ZipArchive::new(file)?;
 
-    for i in 0..zip.len() {
-        let mut file = zip.by_index(i)?;
-        let outpath = dest_path.join(file.mangled_name());
-
-        if file.name().ends_with('/') {
-            fs::create_dir_all(&outpath)?;
-        } else {
-            if let Some(p) = outpath.parent() {
-                fs::create_dir_all(p)?;
+    match format {
+        ArchiveFormat::Zip => {
+            let mut zip = ZipArchive::new(file)?;
+            let root = fs::canonicalize(&dest_path)?;
+
+            for i in 0..zip.len() {
+                let mut file = zip.by_index(i)?;
+                let is_dir = file.is_dir();
+                let outpath =
+                    contained_output_path(&root, file.name(), file.enclosed_name(), is_dir)?;
+
+                if !is_dir {
+                    verbose!("  extracting {}", file.name());
+                    let mut outfile = fs::File::create(&outpath)?;
+                    io::copy(&mut file, &mut outfile)?;
+                }
+            }
+        }
+        ArchiveFormat::Tgz => {
+            let mut tar = tar::Archive::new(GzDecoder::new(file));
+            for entry in tar.entries()? {
+                let mut entry = entry?;
+                verbose!("  extracting {}", entry.path()?.display());
+                // `unpack_in` refuses (returns false) entries that would escape
+                if !entry.unpack_in(&dest_path)? {
+                    return Err(anyhow!(
+                        "Archive entry '{}' would be extracted outside '{}'",
+                        entry.path()?.display(),
+                        dest_path.display()
+                    ));
+                }
             }
-            let mut outfile = fs::File::create(&outpath)?;
-            io::copy(&mut file, &mut outfile)?;
         }
     }
 
     // Create symlink in ~/projects if restoring outside of projects
-    let projects_dir = dirs::home_dir()
-        .ok_or_else(|| anyhow!("Failed to locate home directory"))?
-        .join("projects");
+    let projects_dir = project::projects_dir()?;
     if !dest_path.starts_with(&projects_dir) {
         let symlink_path = projects_dir.join(original_name);
         if symlink_path.exists() {
             fs::remove_file(&symlink_path)?;
         }
         std::os::unix::fs::symlink(&dest_path, &symlink_path)?;
-        println!(
+        info!(
             "🔗 Created symlink from '{}' → '{}'",
             symlink_path.display(),
             dest_path.display()
         );
     }
 
-    println!(
+    info!(
         "✅ Restored archive '{}' to '{}'",
         archive_name,
         dest_path.display()
@@ -1,4 +1,4 @@
-use clap::{Args, Subcommand};
+use clap::{Args, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
 #[derive(Args, Debug)]
@@ -32,56 +32,382 @@ pub enum TodoAction {
     /// Remove a todo by index or text
     #[command(alias = "r")]
     Remove { pattern: String },
+
+    /// Remove completed todos
+    Clear {
+        /// Remove every todo, not just completed ones
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+#[derive(Args, Debug)]
+pub struct CloneArgs {
+    pub source: String,
+
+    pub dest: Option<String>,
+
+    #[arg(short, long)]
+    pub git_clone: bool,
+
+    /// Append -1, -2, ... to the destination name instead of failing when it exists
+    #[arg(long)]
+    pub auto_rename: bool,
+
+    /// Shallow-clone git URLs with only the last N commits
+    #[arg(long, value_name = "N")]
+    pub depth: Option<u32>,
+
+    /// Keep the source's project.json name and completion for local copies
+    #[arg(long)]
+    pub preserve: bool,
+
+    /// Apply a boilr template over the clone and record it in project.json
+    #[arg(short, long)]
+    pub template: Option<String>,
+
+    /// Let boilr prompt for the template values
+    #[arg(short, long, requires = "template")]
+    pub interactive: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct ListArgs {
+    /// Statuses to show, comma separated (e.g. `active,paused`) or `all`
+    /// (defaults to `default_list_status` from the config, else `active`)
+    #[arg(short, long)]
+    pub status: Option<String>,
+
+    /// Show progress bars
+    #[arg(short, long)]
+    pub progress: bool,
+
+    /// Progress bar width in columns (defaults to fitting the terminal)
+    #[arg(
+        long,
+        value_name = "COLS",
+        requires = "progress",
+        value_parser = clap::value_parser!(u16).range(1..)
+    )]
+    pub bar_width: Option<u16>,
+
+    /// Recheck unpushed commits instead of using the per-project cache
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Only show projects created from this template
+    #[arg(short, long)]
+    pub template: Option<String>,
+
+    /// Only show projects whose name matches a glob, e.g. `api-*`
+    #[arg(short, long)]
+    pub filter: Option<String>,
+
+    /// Print only the canonical path of each project, one per line
+    #[arg(long, conflicts_with = "progress")]
+    pub path: bool,
+
+    /// Print totals per status and the average completion at the end
+    #[arg(long, conflicts_with = "path")]
+    pub summary: bool,
+
+    /// Only show projects untouched for at least this many days
+    #[arg(long, value_name = "DAYS")]
+    pub stale: Option<u64>,
+
+    /// Only show git projects with untracked, uncommitted or unpushed changes
+    #[arg(long, alias = "modified-only")]
+    pub dirty: bool,
 }
 
 #[derive(Subcommand, Debug)]
-pub enum Commands {
-    /// initialise the current directory as a project
-    Init {
+pub enum ConfigAction {
+    /// Print a config value, or the whole config without a key
+    Get { key: Option<String> },
+
+    /// Set a config value
+    Set { key: String, value: String },
+
+    /// Print the config file location
+    Path,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TemplateAction {
+    /// Apply a boilr template to the current project and record it in project.json
+    Apply {
+        name: String,
+
+        /// Replace a template already recorded in project.json
         #[arg(short, long)]
-        interactive: bool,
+        force: bool,
+
+        /// Let boilr prompt for the template values
         #[arg(short, long)]
-        template: Option<String>,
-        #[arg(value_parser = parse_key_val::<String, String>)]
-        vars: Vec<(String, String)>,
+        interactive: bool,
+
+        /// Search this directory for templates before the configured ones
+        #[arg(long)]
+        template_dir: Option<PathBuf>,
+
+        /// Pass an extra variable to the boilr template without saving it to project.json
+        #[arg(long = "set", value_name = "KEY=VALUE", value_parser = parse_key_val::<String, String>)]
+        template_vars: Vec<(String, String)>,
     },
+}
+
+#[derive(Args, Debug)]
+pub struct InitArgs {
+    #[arg(short, long)]
+    pub interactive: bool,
+
+    #[arg(short, long)]
+    pub template: Option<String>,
+
+    /// Don't apply or ask for a boilr template
+    #[arg(long, conflicts_with = "template")]
+    pub no_template: bool,
+
+    /// Search this directory for templates before the configured ones
+    #[arg(long)]
+    pub template_dir: Option<PathBuf>,
+
+    #[arg(value_parser = parse_key_val::<String, String>)]
+    pub vars: Vec<(String, String)>,
+
+    /// Pass an extra variable to the boilr template without saving it to project.json
+    #[arg(long = "set", value_name = "KEY=VALUE", value_parser = parse_key_val::<String, String>)]
+    pub template_vars: Vec<(String, String)>,
+
+    /// Rerun the git, upstream and template steps in an existing project
+    #[arg(long)]
+    pub reinit: bool,
+
+    /// Commit everything and push to origin after initializing
+    #[arg(long)]
+    pub git_push: bool,
+
+    /// Create the upstream repository without asking
+    #[arg(long, conflicts_with = "no_upstream")]
+    pub upstream: bool,
+
+    /// Never ask to create an upstream repository
+    #[arg(long)]
+    pub no_upstream: bool,
+
+    /// Hosting provider for the upstream repository (detected from installed CLIs by default)
+    #[arg(long, value_enum)]
+    pub provider: Option<Provider>,
+
+    /// Add this URL as `origin` instead of creating a repository with a provider CLI
+    #[arg(long, conflicts_with = "provider")]
+    pub remote: Option<String>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Provider {
+    /// GitHub, through the `gh` CLI
+    Github,
+    /// GitLab, through the `glab` CLI
+    Gitlab,
+}
+
+impl Provider {
+    /// The CLI used to create repositories on this provider
+    pub fn cli(self) -> &'static str {
+        match self {
+            Provider::Github => "gh",
+            Provider::Gitlab => "glab",
+        }
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Provider::Github => "GitHub",
+            Provider::Gitlab => "GitLab",
+        }
+    }
+
+    /// The first provider whose CLI is installed, preferring GitHub
+    pub fn detect() -> Option<Provider> {
+        [Provider::Github, Provider::Gitlab].into_iter().find(|p| {
+            std::process::Command::new(p.cli())
+                .arg("--version")
+                .stdout(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
+                .status()
+                .is_ok()
+        })
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Zip,
+    /// Gzipped tarball, keeping Unix permissions and mtimes
+    Tgz,
+}
+
+/// Semver component incremented by `project bump`
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BumpPart {
+    Major,
+    Minor,
+    Patch,
+}
+
+impl ArchiveFormat {
+    /// File extension used for archives in this format
+    pub fn extension(self) -> &'static str {
+        match self {
+            ArchiveFormat::Zip => "zip",
+            ArchiveFormat::Tgz => "tar.gz",
+        }
+    }
+}
+
+impl InitArgs {
+    /// The template to apply: `--template`, else `default_template` from the
+    /// config, and none at all with `--no-template`
+    pub fn template_name(&self) -> Option<String> {
+        if self.no_template {
+            return None;
+        }
+        self.template
+            .clone()
+            .or_else(|| crate::config::load().default_template)
+    }
+
+    /// The upstream choice made on the command line, if any
+    pub fn upstream_choice(&self) -> Option<bool> {
+        match (self.upstream, self.no_upstream) {
+            (true, _) => Some(true),
+            (_, true) => Some(false),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Commands {
+    /// initialise the current directory as a project
+    Init(InitArgs),
     /// Create a new project
+    ///
+    /// A bare name is created in ~/projects; a path containing `/` is created
+    /// at that location and symlinked into ~/projects.
     Create {
         name: String,
-        #[arg(short, long)]
-        template: Option<String>,
-        #[arg(value_parser = parse_key_val::<String, String>)]
-        vars: Vec<(String, String)>,
-        #[arg(short, long)]
-        interactive: bool,
+
+        /// Create the project in the current directory instead of ~/projects
+        #[arg(long)]
+        here: bool,
+
+        #[command(flatten)]
+        init: InitArgs,
     },
     /// Scan for projects
     Scan {
         #[arg(short, long)]
         recursive: bool,
+
+        /// Limit how many directory levels a recursive scan descends (implies --recursive)
+        #[arg(short, long)]
+        depth: Option<usize>,
+
+        /// Symlink discovered projects into ~/projects so `list` shows them
+        #[arg(long)]
+        register: bool,
+
+        /// Print one JSON object per project (name and canonical path) instead
+        #[arg(long, conflicts_with = "register")]
+        json: bool,
     },
     /// Set a project variable
     Set {
         #[arg(value_parser = parse_key_val::<String, String>)]
         vars: Vec<(String, String)>,
+
+        /// Store values as strings instead of detecting bools and numbers
+        #[arg(short, long)]
+        string: bool,
     },
-    /// Get a project variable
-    Get {
-        key: String,
+    /// Remove project variables (dot notation reaches nested keys)
+    Unset {
+        #[arg(required = true)]
+        keys: Vec<String>,
     },
-    /// list all projects
-    List {
-        #[arg(short, long, default_value = "active")]
+    /// Set the status of several projects at once
+    SetStatus {
         status: String,
+        #[arg(required = true)]
+        names: Vec<String>,
+    },
+    /// Get a project variable (prints all variables when no key is given)
+    Get {
+        key: Option<String>,
+
+        /// List only the top-level keys
+        #[arg(short, long, conflicts_with = "key")]
+        keys: bool,
 
-        /// Show progress bars
+        /// Print this instead of failing when the key is missing
+        #[arg(short, long, requires = "key")]
+        default: Option<String>,
+    },
+    /// Increment the project's semantic version
+    Bump {
+        #[arg(value_enum)]
+        part: BumpPart,
+    },
+    /// Create an annotated git tag `v<version>` from project.json
+    Tag {
+        /// Push the tag to origin
+        #[arg(long)]
+        push: bool,
+    },
+    /// Stage and commit everything in every project with uncommitted changes
+    CommitAll {
+        /// Commit message
         #[arg(short, long)]
-        progress: bool,
+        message: String,
+    },
+    /// Run `git push` in every project with unpushed commits
+    PushAll {
+        /// Only push projects with this status
+        #[arg(short, long)]
+        status: Option<String>,
+    },
+    /// Show the details of the current project
+    Status,
+    /// Show the details of any project by name
+    Info {
+        name: String,
+    },
+    /// Print the canonical path of a project, for use in scripts (exits 1 if not found)
+    Which {
+        name: String,
+    },
+
+    /// Check ~/projects for broken symlinks and invalid project files
+    Doctor {
+        /// Remove dead symlinks and backfill missing keys
+        #[arg(long)]
+        fix: bool,
+    },
+    /// list all projects
+    List(ListArgs),
+    /// Show the most recently active projects
+    Recent {
+        /// Number of projects to show
+        #[arg(short, long, default_value_t = 10)]
+        limit: usize,
     },
     /// Move a project to destination (defaults to ~/projects/<project name>/)
     Migrate {
         /// Name of the project to move
-        name: String,
+        #[arg(required_unless_present = "undo")]
+        name: Option<String>,
 
         /// Optional destination directory (defaults to ~/projects)
         #[arg(short, long)]
@@ -90,6 +416,18 @@ pub enum Commands {
         /// If set, copy instead of move
         #[arg(short, long)]
         copy: bool,
+
+        /// Move the last migrated project back to where it came from
+        #[arg(long, conflicts_with_all = ["name", "destination", "copy"])]
+        undo: bool,
+    },
+    /// Rename a project, its folder and its ~/projects symlink
+    Rename {
+        /// Current name of the project
+        old: String,
+
+        /// New name for the project
+        new: String,
     },
     /// Remove a project
     Remove {
@@ -99,23 +437,81 @@ pub enum Commands {
         /// Force removal without confirmation
         #[arg(short, long)]
         force: bool,
+
+        /// Delete permanently instead of moving to ~/.proj/trash
+        #[arg(long)]
+        purge: bool,
     },
     /// Clone a project from github
-    Clone {
-        source: String,
-
-        dest: Option<String>,
+    Clone(CloneArgs),
+    /// Run a command from the `scripts` object in project.json
+    Run {
+        script: String,
 
-        #[arg(short, long)]
-        git_clone: bool,
+        /// Extra arguments passed on to the command
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Print `export` lines for a project's .env and project.json `env`, for
+    /// use with `eval "$(project env <name>)"` (defaults to the current project)
+    Env {
+        name: Option<String>,
+    },
+    /// Open a project in $VISUAL / $EDITOR (defaults to the current project)
+    Open {
+        name: Option<String>,
+    },
+    /// Open the web page of a project's origin remote (defaults to the current project)
+    OpenUrl {
+        name: Option<String>,
     },
     /// Archive a project
     Archive {
         name: String,
         destination: Option<PathBuf>, // optional archive directory
+
+        /// Keep the project in place after archiving (snapshot only)
+        #[arg(short, long)]
+        keep: bool,
+
+        /// Archive format
+        #[arg(short, long, value_enum, default_value_t = ArchiveFormat::Zip)]
+        format: ArchiveFormat,
+    },
+    /// Archive every project with the given status
+    ArchiveAll {
+        /// Status of the projects to archive, e.g. `done`
+        #[arg(short, long)]
+        status: String,
+
+        /// Keep the projects in place after archiving (snapshot only)
+        #[arg(short, long)]
+        keep: bool,
+
+        /// Archive format
+        #[arg(short, long, value_enum, default_value_t = ArchiveFormat::Zip)]
+        format: ArchiveFormat,
+    },
+    /// Write a project to an archive file without removing it
+    Export {
+        name: String,
+
+        /// Destination file; `.tar.gz` or `.tgz` selects a tarball, otherwise zip
+        file: PathBuf,
+    },
+    /// List all archived projects, newest first
+    Archives {
+        /// Print the archives as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Delete old archives, keeping only the newest ones of each project
+    ArchivePrune {
+        /// Number of archives to keep per project
+        #[arg(short, long, default_value_t = 3)]
+        keep: usize,
     },
-    /// List all archived projects
-    Archives,
 
     /// Remove a specific archived project
     ArchiveRemove {
@@ -127,11 +523,45 @@ pub enum Commands {
         name: String,
         #[arg(short, long)]
         destination: Option<String>,
+
+        /// Treat `name` as a project name and restore its newest archive
+        #[arg(short, long)]
+        latest: bool,
+
+        /// Replace an existing destination without asking
+        #[arg(short, long, conflicts_with = "merge")]
+        force: bool,
+
+        /// Extract into an existing destination, keeping files not in the archive
+        #[arg(short, long)]
+        merge: bool,
     },
 
     Todo(TodoArgs),
 
+    /// Add a note to the project log, or print the log newest-first
+    Log {
+        message: Option<String>,
+    },
+
+    /// View and change global settings
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
+    /// Work with boilr templates
+    Template {
+        #[command(subcommand)]
+        action: TemplateAction,
+    },
+
     Initshell,
+
+    /// Print a shell completion script
+    Completions {
+        shell: clap_complete::Shell,
+    },
 }
 
 pub fn parse_key_val<T, U>(s: &str) -> Result<(T, U), String>
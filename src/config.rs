@@ -0,0 +1,156 @@
+use crate::errors;
+use crate::output::info;
+use crate::utils;
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Global settings read from `~/.config/project/config.toml`
+#[derive(Serialize, Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct Config {
+    /// Extra directories searched for boilr templates
+    pub template_dirs: Vec<PathBuf>,
+    /// Where projects are linked (defaults to `~/projects`)
+    pub projects_dir: Option<PathBuf>,
+    /// Template used by `init` and `create` when none is given
+    pub default_template: Option<String>,
+    /// Editor for `open` when $VISUAL and $EDITOR are unset
+    pub editor: Option<String>,
+    /// Mark a project `done` when `set completion=1.0` and `active` again
+    /// when completion drops below 1.0
+    pub auto_status_on_complete: bool,
+    /// Color used for each status in `list`, e.g. `paused = "yellow"`
+    pub status_colors: BTreeMap<String, String>,
+    /// Statuses `list` shows when `--status` isn't given (defaults to `active`)
+    pub default_list_status: Option<String>,
+}
+
+/// Keys accepted by `project config get/set`
+const KEYS: &[&str] = &[
+    "template_dirs",
+    "projects_dir",
+    "default_template",
+    "editor",
+    "auto_status_on_complete",
+    "status_colors",
+    "default_list_status",
+];
+
+/// Return the config file location (`~/.config/project/config.toml`)
+pub fn config_path() -> Result<PathBuf> {
+    Ok(utils::home_dir()?.join(".config/project/config.toml"))
+}
+
+/// Return the location of the scan ignore file (`~/.config/project/ignore`)
+pub fn ignore_path() -> Result<PathBuf> {
+    Ok(config_path()?.with_file_name("ignore"))
+}
+
+/// Read the directory globs in the ignore file, skipping blank lines and `#`
+/// comments. A leading `~/` is expanded to the home directory
+pub fn ignore_patterns() -> Vec<String> {
+    let (Ok(home), Ok(path)) = (utils::home_dir(), ignore_path()) else {
+        return Vec::new();
+    };
+    fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| {
+            let l = l.trim_end_matches('/');
+            match l.strip_prefix("~/") {
+                Some(rest) => home.join(rest).to_string_lossy().into_owned(),
+                None => l.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Load the config, falling back to defaults when it is missing or invalid
+pub fn load() -> Config {
+    let Ok(path) = config_path() else {
+        return Config::default();
+    };
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Config::default();
+    };
+
+    toml::from_str(&content).unwrap_or_else(|e| {
+        eprintln!("⚠️  Ignoring invalid config {}: {}", path.display(), e);
+        Config::default()
+    })
+}
+
+fn check_key(key: &str) -> Result<()> {
+    if KEYS.contains(&key) {
+        Ok(())
+    } else {
+        Err(errors::not_found(format!(
+            "Unknown config key '{}'. Valid keys: {}",
+            key,
+            KEYS.join(", ")
+        )))
+    }
+}
+
+/// Read the config file as a raw table, keeping keys this version doesn't know
+fn load_table() -> Result<toml::Table> {
+    let path = config_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => {
+            toml::from_str(&content).with_context(|| format!("Invalid config {}", path.display()))
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(toml::Table::new()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+/// Print one config value, or the whole config without a key
+pub fn get(key: Option<&str>) -> Result<()> {
+    let table = load_table()?;
+
+    let Some(key) = key else {
+        print!("{}", toml::to_string_pretty(&table)?);
+        return Ok(());
+    };
+
+    check_key(key)?;
+    match table.get(key) {
+        Some(toml::Value::String(s)) => println!("{}", s),
+        Some(value) => println!("{}", value),
+        None => eprintln!("Key '{}' is not set.", key),
+    }
+    Ok(())
+}
+
+/// Set a config value; TOML syntax is accepted (e.g. `["a", "b"]` for a
+/// list), anything else is stored as a string
+pub fn set(key: &str, value: &str) -> Result<()> {
+    check_key(key)?;
+    let mut table = load_table()?;
+
+    let parsed = toml::from_str::<toml::Table>(&format!("value = {}", value))
+        .ok()
+        .and_then(|mut t| t.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(value.to_string()));
+    table.insert(key.to_string(), parsed);
+
+    // Make sure the result still loads before writing it
+    toml::Value::Table(table.clone())
+        .try_into::<Config>()
+        .map_err(|e| anyhow!("Invalid value for '{}': {}", key, e.message()))?;
+
+    let path = config_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, toml::to_string_pretty(&table)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    info!("✅ Set {} in {}", key, path.display());
+    Ok(())
+}
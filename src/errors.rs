@@ -0,0 +1,58 @@
+use std::fmt;
+use std::io;
+
+/// Error categories with their own process exit codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    /// A project, archive, template or key doesn't exist
+    NotFound,
+    /// The target of an operation already exists
+    Conflict,
+}
+
+#[derive(Debug)]
+pub struct CategorizedError {
+    pub category: Category,
+    message: String,
+}
+
+impl fmt::Display for CategorizedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for CategorizedError {}
+
+pub fn not_found(message: impl fmt::Display) -> anyhow::Error {
+    CategorizedError {
+        category: Category::NotFound,
+        message: message.to_string(),
+    }
+    .into()
+}
+
+pub fn conflict(message: impl fmt::Display) -> anyhow::Error {
+    CategorizedError {
+        category: Category::Conflict,
+        message: message.to_string(),
+    }
+    .into()
+}
+
+/// Exit code for a failed command: 2 when something wasn't found, 3 on a
+/// conflict, 4 for I/O errors and 1 for anything else
+pub fn exit_code(err: &anyhow::Error) -> i32 {
+    for cause in err.chain() {
+        if let Some(e) = cause.downcast_ref::<CategorizedError>() {
+            return match e.category {
+                Category::NotFound => 2,
+                Category::Conflict => 3,
+            };
+        }
+        if cause.is::<io::Error>() {
+            return 4;
+        }
+    }
+    1
+}
@@ -1,8 +1,8 @@
 pub fn detect_shell() -> String {
-    if let Ok(_) = std::env::var("BASH") {
+    if std::env::var("BASH").is_ok() {
         return "bash".into();
     }
-    if let Ok(_) = std::env::var("ZSH_NAME") {
+    if std::env::var("ZSH_NAME").is_ok() {
         return "zsh".into();
     }
     std::env::var("SHELL")
@@ -54,10 +54,69 @@ alias todo=\"project todo\"
 alias projects=\"cd ~/projects/\"
 ";
             println!("{}", code);
+
+            // Tab-complete the first argument with the names in ~/projects
+            let completion = if shell == "bash" {
+                "
+_project_complete() {
+    if [ \"$COMP_CWORD\" -eq 1 ]; then
+        local projects
+        projects=$(cd \"$HOME/projects\" 2>/dev/null && ls -d -- */ 2>/dev/null | sed 's#/$##')
+        COMPREPLY=($(compgen -W \"$projects\" -- \"${COMP_WORDS[1]}\"))
+    fi
+}
+complete -F _project_complete project
+"
+            } else {
+                "
+_project() {
+    if (( CURRENT == 2 )); then
+        local -a projects
+        projects=($HOME/projects/*(N-/:t))
+        _describe 'project' projects
+    fi
+}
+if (( $+functions[compdef] )); then
+    compdef _project project
+fi
+"
+            };
+            println!("{}", completion);
         }
         "fish" => {
             let code = "
 
+function project
+    # If no args, just call the CLI
+    if test (count $argv) -eq 0
+        command project
+        return
+    end
+
+    set -l proj_name $argv[1]
+    set -l proj_dir \"$HOME/projects/$proj_name\"
+
+    if test -d \"$proj_dir\"
+        set -l real_path (readlink -f \"$proj_dir\")
+        cd \"$real_path\"; or return
+        # Optionally activate .env if it exists
+        if test -f \".env\"
+            for line in (grep -v '^\\s*#' .env | grep '=')
+                set -l kv (string split -m 1 = -- (string replace -r '^export\\s+' '' -- $line))
+                set -gx $kv[1] (string trim -c '\"\\'' -- $kv[2])
+            end
+        end
+        # Print status
+        command project list | grep \"^$proj_name\"
+    else
+        # Not a project dir, pass everything to Rust CLI
+        command project $argv
+    end
+end
+
+
+abbr -a todo project todo
+alias projects=\"cd ~/projects/\"
 ";
 
             println!("{}", code);
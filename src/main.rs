@@ -1,13 +1,16 @@
 mod archive;
 mod climod;
+mod config;
+mod errors;
 mod initshell;
+mod output;
 mod project;
 mod template;
 mod todo;
 mod utils;
  
 use anyhow::Result;
-use clap::Parser;
+use clap::{CommandFactory, Parser};
 
 
 /// Project — a project management and orchestration CLI tool
@@ -18,65 +21,154 @@ use clap::Parser;
 pub struct Cli {
     #[command(subcommand)]
     command: climod::Commands,
+
+    /// Print what destructive commands would do without changing anything
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    /// Print extra detail, such as every file added to an archive
+    #[arg(long, global = true, conflicts_with = "quiet")]
+    verbose: bool,
+
+    /// Only print requested output and errors
+    #[arg(long, global = true)]
+    quiet: bool,
 }
 
-fn main() -> Result<()> {
+fn main() {
     let cli = Cli::parse();
 
+    if cli.quiet {
+        output::set_level(output::Level::Quiet);
+    } else if cli.verbose {
+        output::set_level(output::Level::Verbose);
+    }
+
+    if let Err(e) = run(&cli) {
+        eprintln!("error: {:#}", e);
+        std::process::exit(errors::exit_code(&e));
+    }
+}
+
+fn run(cli: &Cli) -> Result<()> {
     match &cli.command {
-        climod::Commands::Init {
-            interactive,
-            template,
-            vars,
-        } => {
-            project::ensure_projects_dir().unwrap();
-            project::init_project(*interactive, template.clone(), vars);
+        climod::Commands::Init(init) => {
+            project::ensure_projects_dir()?;
+            project::init_project(init)?;
         }
-        climod::Commands::Create {
-            name,
-            template,
-            vars,
-            interactive,
-        } => {
-            project::ensure_projects_dir().unwrap();
-            project::create_project(name, template.clone(), vars, *interactive);
+        climod::Commands::Create { name, here, init } => {
+            project::ensure_projects_dir()?;
+            project::create_project(name, *here, init)?;
+        }
+        climod::Commands::Scan {
+            recursive,
+            depth,
+            register,
+            json,
+        } => project::scan_for_proj(*recursive, *depth, *register, *json)?,
+        climod::Commands::Set { vars, string } => project::set_project_vars(vars, *string)?,
+        climod::Commands::Unset { keys } => project::unset_vars(keys)?,
+        climod::Commands::SetStatus { status, names } => project::set_status(status, names)?,
+        climod::Commands::Get { key, keys, default } => {
+            project::get_project_var(key.as_deref(), *keys, default.as_deref())?
         }
-        climod::Commands::Scan { recursive } => project::scan_for_proj(*recursive),
-        climod::Commands::Set { vars } => project::set_project_vars(vars),
-        climod::Commands::Get { key } => project::get_project_var(key),
-        climod::Commands::List { status, progress } => project::list_projects(status, *progress),
+        climod::Commands::Bump { part } => project::bump_version(*part)?,
+        climod::Commands::Tag { push } => project::tag_version(*push)?,
+        climod::Commands::CommitAll { message } => project::commit_all(message, cli.dry_run)?,
+        climod::Commands::PushAll { status } => project::push_all(status.as_deref(), cli.dry_run)?,
+        climod::Commands::Status => project::show_status()?,
+        climod::Commands::Info { name } => project::show_info(name)?,
+        climod::Commands::Which { name } => project::which_project(name)?,
+        climod::Commands::Doctor { fix } => project::doctor(*fix)?,
+        climod::Commands::List(list) => project::list_projects(list),
+        climod::Commands::Recent { limit } => project::recent_projects(*limit),
         climod::Commands::Migrate {
             name,
             destination,
-            copy: _,
-        } => project::migrate_project(name, destination.clone()).expect("Migration failed"),
-        climod::Commands::Remove { name, force } => {
-            project::remove_project(name, *force).expect("Failed to remove project")
+            copy,
+            undo,
+        } => match name {
+            Some(name) if !*undo => {
+                project::migrate_project(name, destination.clone(), *copy, cli.dry_run)?
+            }
+            _ => project::undo_migrate(cli.dry_run)?,
+        },
+        climod::Commands::Rename { old, new } => project::rename_project(old, new)?,
+        climod::Commands::Remove { name, force, purge } => {
+            project::remove_project(name, *force, *purge, cli.dry_run)?
         }
-        climod::Commands::Clone {
-            source,
-            dest,
-            git_clone,
-        } => project::clone_project(source, dest.as_deref(), *git_clone)
-            .expect("Failed to clone project"),
-        climod::Commands::Archive { name, .. } => {
-            archive::archive_project(name).expect("Failed to archive project")
+        climod::Commands::Clone(clone) => project::clone_project(clone)?,
+        climod::Commands::Run { script, args } => project::run_script(script, args)?,
+        climod::Commands::Env { name } => project::print_env(name.as_deref())?,
+        climod::Commands::Open { name } => project::open_project(name.as_deref())?,
+        climod::Commands::OpenUrl { name } => project::open_url(name.as_deref())?,
+        climod::Commands::Archive {
+            name, keep, format, ..
+        } => archive::archive_project(name, *keep, *format, cli.dry_run)?,
+        climod::Commands::ArchiveAll {
+            status,
+            keep,
+            format,
+        } => archive::archive_all(status, *keep, *format, cli.dry_run)?,
+        climod::Commands::Export { name, file } => {
+            archive::export_project(name, file, cli.dry_run)?
         }
-        climod::Commands::Archives => archive::list_archives()?,
+        climod::Commands::Archives { json } => archive::list_archives(*json)?,
         climod::Commands::ArchiveRemove { name } => archive::remove_archive(name)?,
-        climod::Commands::Restore { name, destination } => {
-            archive::restore_archive(name, destination.as_deref())?
+        climod::Commands::ArchivePrune { keep } => archive::prune_archives(*keep, cli.dry_run)?,
+        climod::Commands::Restore {
+            name,
+            destination,
+            latest,
+            force,
+            merge,
+        } => {
+            if *latest {
+                let archive_name = archive::find_latest_archive(name)?;
+                archive::restore_archive(&archive_name, destination.as_deref(), *force, *merge)?
+            } else {
+                archive::restore_archive(name, destination.as_deref(), *force, *merge)?
+            }
         }
         climod::Commands::Initshell => {
             let shell = initshell::detect_shell();
             initshell::init_shell(&shell);
         }
+        climod::Commands::Completions { shell } => {
+            clap_complete::generate(
+                *shell,
+                &mut Cli::command(),
+                "project",
+                &mut std::io::stdout(),
+            );
+        }
+        climod::Commands::Config { action } => match action {
+            climod::ConfigAction::Get { key } => config::get(key.as_deref())?,
+            climod::ConfigAction::Set { key, value } => config::set(key, value)?,
+            climod::ConfigAction::Path => println!("{}", config::config_path()?.display()),
+        },
+        climod::Commands::Template { action } => match action {
+            climod::TemplateAction::Apply {
+                name,
+                force,
+                interactive,
+                template_dir,
+                template_vars,
+            } => project::apply_template(
+                name,
+                *force,
+                *interactive,
+                template_dir.as_deref(),
+                template_vars,
+            )?,
+        },
         climod::Commands::Todo(todoargs) => {
             if let Some(action) = &todoargs.action {
                 match action {
                     climod::TodoAction::List => todo::todo_list()?,
                     climod::TodoAction::Add { text } => todo::todo_add(text)?,
                     climod::TodoAction::Remove { pattern } => todo::todo_remove(pattern)?,
+                    climod::TodoAction::Clear { all } => todo::todo_clear(*all)?,
                 }
             } else if todoargs.list_flag {
                 todo::todo_list()?;
@@ -88,6 +180,7 @@ fn main() -> Result<()> {
                 todo::todo_list()?;
             }
         }
+        climod::Commands::Log { message } => project::log(message.as_deref())?,
     }
     Ok(())
 }
@@ -0,0 +1,61 @@
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much informational output to print
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// Only requested data, prompts and errors
+    Quiet,
+    Normal,
+    /// Extra detail such as per-file archive progress
+    Verbose,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Normal as u8);
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Whether messages at `level` should be printed
+pub fn enabled(level: Level) -> bool {
+    LEVEL.load(Ordering::Relaxed) >= level as u8
+}
+
+/// Whether to emit ANSI colors: only when stdout is a terminal, so piped
+/// output (e.g. the initshell wrapper's `project list | grep`) stays plain,
+/// and never when `NO_COLOR` is set to anything non-empty
+/// (https://no-color.org)
+pub fn color_enabled() -> bool {
+    std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+}
+
+/// Wrap `text` in the ANSI SGR color `code` (e.g. `"32"` for green) unless
+/// colors are disabled
+pub fn paint(text: &str, code: &str) -> String {
+    if text.is_empty() || !color_enabled() {
+        text.to_string()
+    } else {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    }
+}
+
+/// Print a status line (`✅ Done` and the like), hidden by `--quiet`
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if $crate::output::enabled($crate::output::Level::Normal) {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Print extra detail, shown only with `--verbose`
+macro_rules! verbose {
+    ($($arg:tt)*) => {
+        if $crate::output::enabled($crate::output::Level::Verbose) {
+            println!($($arg)*);
+        }
+    };
+}
+
+pub(crate) use {info, verbose};
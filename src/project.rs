@@ -1,20 +1,27 @@
+use crate::climod::{BumpPart, CloneArgs, InitArgs, ListArgs, Provider};
+use crate::config;
+use crate::errors;
+use crate::output::{self, info};
 use crate::template;
+use crate::todo;
 use crate::utils;
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow};
 use serde_json::{Value, json};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::env;
 use std::fs;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::sync::OnceLock;
 
 pub fn find_project_path(name: &str) -> Option<PathBuf> {
-    let projects_dir = dirs::home_dir()?.join("projects");
     let mut found = None;
 
     // Look for a folder matching the name
-    if let Ok(entries) = fs::read_dir(&projects_dir) {
+    if let Ok(projects_dir) = projects_dir()
+        && let Ok(entries) = fs::read_dir(&projects_dir)
+    {
         for entry in entries.flatten() {
             let path = entry.path();
             if path.file_name().and_then(|f| f.to_str()) == Some(name) {
@@ -27,17 +34,131 @@ pub fn find_project_path(name: &str) -> Option<PathBuf> {
         }
     }
 
-    found
+    if found.is_some() {
+        return found;
+    }
+
+    // Fall back to a recursive search, matching the folder or the `name` field
+    collect_projects().into_iter().find(|path| {
+        path.file_name().and_then(|f| f.to_str()) == Some(name)
+            || utils::read_json(&path.join(".proj/project.json"))
+                .unwrap_or_default()
+                .get("name")
+                .and_then(|v| v.as_str())
+                == Some(name)
+    })
+}
+
+/// Directories never worth descending into when looking for projects
+const SKIP_DIRS: &[&str] = &["node_modules", "target", ".cache"];
+
+/// Globs from `~/.config/project/ignore`, read once per run
+static IGNORE_PATTERNS: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Whether `path` matches a user ignore pattern. Patterns containing `/`
+/// match the full path, others match the directory name
+fn is_user_ignored(path: &Path) -> bool {
+    let patterns = IGNORE_PATTERNS.get_or_init(config::ignore_patterns);
+    if patterns.is_empty() {
+        return false;
+    }
+
+    let full = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let full = full.to_string_lossy();
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    patterns.iter().any(|pattern| {
+        if pattern.contains('/') {
+            utils::glob_match(pattern, &full)
+        } else {
+            utils::glob_match(pattern, &name)
+        }
+    })
+}
+
+/// Whether a directory walk should skip `path`: hidden folders (including
+/// `.git`), heavy build or cache directories and anything in the ignore file
+fn should_skip_dir(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.starts_with('.') || SKIP_DIRS.contains(&n))
+        || is_user_ignored(path)
+}
+
+/// Canonicalize `path` and record it in `seen`, returning `None` when the
+/// directory was already visited through another root or symlink
+fn first_visit(path: &Path, seen: &mut HashSet<PathBuf>) -> Option<PathBuf> {
+    let real_path = fs::canonicalize(path).ok()?;
+    seen.insert(real_path.clone()).then_some(real_path)
+}
+
+/// Recursively scan directories for projects
+fn visit_projects(dir: &Path, recursive: bool, seen: &mut HashSet<PathBuf>) -> Vec<PathBuf> {
+    let mut projects = Vec::new();
+
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+
+            if !path.is_dir() || should_skip_dir(&path) {
+                continue;
+            }
+            let Some(real_path) = first_visit(&path, seen) else {
+                continue;
+            };
+
+            if path.join(".proj/project.json").is_file() {
+                projects.push(real_path);
+            } else if recursive {
+                projects.extend(visit_projects(&path, recursive, seen));
+            }
+        }
+    }
+
+    projects
+}
+
+/// Collect the canonical paths of all projects under the current directory and `~/projects`
+pub fn collect_projects() -> Vec<PathBuf> {
+    let mut seen = HashSet::new();
+
+    // Scan current directory and ~/projects
+    let mut all_projects = visit_projects(Path::new("."), true, &mut seen);
+    if let Ok(dir) = projects_dir() {
+        all_projects.extend(visit_projects(&dir, true, &mut seen));
+    }
+    all_projects
+}
+
+/// Environment variable that overrides the projects directory and moves the
+/// archives under it, e.g. to sandbox a run in a temp dir
+const PROJECT_HOME_VAR: &str = "PROJECT_HOME";
+
+/// `$PROJECT_HOME` when it is set and not empty
+pub fn project_home() -> Option<PathBuf> {
+    env::var_os(PROJECT_HOME_VAR)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
 }
 
-/// Return the central projects directory (`~/projects`)
-pub fn projects_dir() -> PathBuf {
-    dirs::home_dir().unwrap().join("projects")
+/// Return the central projects directory: `$PROJECT_HOME`, else
+/// `projects_dir` from the config, or `~/projects`
+pub fn projects_dir() -> Result<PathBuf> {
+    if let Some(dir) = project_home() {
+        return Ok(dir);
+    }
+
+    Ok(match config::load().projects_dir {
+        Some(dir) => match dir.strip_prefix("~") {
+            Ok(rest) => utils::home_dir()?.join(rest),
+            Err(_) => dir,
+        },
+        None => utils::home_dir()?.join("projects"),
+    })
 }
 
 /// Make sure `~/projects` exists
-pub fn ensure_projects_dir() -> std::io::Result<()> {
-    let dir = projects_dir();
+pub fn ensure_projects_dir() -> Result<()> {
+    let dir = projects_dir()?;
     if !dir.exists() {
         fs::create_dir_all(&dir)?;
     }
@@ -46,7 +167,13 @@ pub fn ensure_projects_dir() -> std::io::Result<()> {
 
 /// Create a symlink in `~/projects` if project is outside of it
 pub fn link_in_projects_dir(project_path: &Path) {
-    let projects = projects_dir();
+    let projects = match projects_dir() {
+        Ok(projects) => projects,
+        Err(e) => {
+            eprintln!("Failed to create symlink: {:#}", e);
+            return;
+        }
+    };
     let proj_name = project_path.file_name().unwrap_or_default();
     let symlink_path = projects.join(proj_name);
 
@@ -63,37 +190,147 @@ pub fn link_in_projects_dir(project_path: &Path) {
         .unwrap_or_else(|e| eprintln!("Failed to create symlink: {}", e));
 }
 
-pub fn maybe_create_upstream(project_name: &str, project_path: &Path) {
-    println!(
-        "Do you want to create a GitHub repository for '{}' and push the current branch? [y/N]: ",
-        project_name
-    );
-    io::stdout().flush().unwrap();
+/// Set up the `origin` remote for a new project
+///
+/// With `--remote <url>` the URL is added as origin directly. Otherwise the
+/// user is offered a new repository through the provider CLI (`gh` or
+/// `glab`); `--upstream`/`--no-upstream` skip the prompt, and without a
+/// terminal on stdin nothing is created.
+pub fn maybe_create_upstream(project_name: &str, project_path: &Path, args: &InitArgs) {
+    if let Some(url) = &args.remote {
+        add_remote(project_path, url);
+        return;
+    }
+
+    let create = match args.upstream_choice() {
+        Some(create) => create,
+        None if !io::stdin().is_terminal() => false,
+        None => {
+            println!(
+                "Do you want to create an upstream repository for '{}' and push the current branch? [y/N]: ",
+                project_name
+            );
+            io::stdout().flush().unwrap();
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input).unwrap();
+            let trimmed = input.trim().to_lowercase();
+            trimmed == "y" || trimmed == "yes"
+        }
+    };
+
+    if !create {
+        return;
+    }
+
+    let Some(provider) = args.provider.or_else(Provider::detect) else {
+        eprintln!(
+            "❌ Neither `gh` (GitHub) nor `glab` (GitLab) is installed. Install one of them or pass --remote <url>."
+        );
+        return;
+    };
+
+    let status = Command::new(provider.cli())
+        .args(["repo", "create"])
+        .current_dir(project_path)
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status();
+
+    match status {
+        Ok(s) if s.success() => info!("✅ {} repo created!", provider.display_name()),
+        Ok(s) => eprintln!("❌ Failed to create repo, exit code {}", s),
+        Err(e) => eprintln!("❌ Failed to run `{}`: {}", provider.cli(), e),
+    }
+}
+
+/// Add `url` as the `origin` remote and push the current branch if it has commits
+fn add_remote(project_path: &Path, url: &str) {
+    let added = Command::new("git")
+        .args(["remote", "add", "origin", url])
+        .current_dir(project_path)
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false);
+    if !added {
+        eprintln!("❌ Failed to add remote '{}'", url);
+        return;
+    }
+    info!("🔗 Added remote origin {}", url);
 
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    let trimmed = input.trim().to_lowercase();
+    let has_commits = Command::new("git")
+        .args(["rev-parse", "--verify", "--quiet", "HEAD"])
+        .current_dir(project_path)
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false);
 
-    if trimmed == "y" || trimmed == "yes" {
-        let status = Command::new("gh")
-            .args(["repo", "create"])
+    if has_commits && let Some(branch) = current_branch(project_path) {
+        let _ = Command::new("git")
+            .args(["push", "--set-upstream", "origin", &branch])
             .current_dir(project_path)
-            .stdin(Stdio::inherit())
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
             .status();
+    }
+}
+
+/// Current layout of project.json, bumped whenever its keys change
+pub const SCHEMA_VERSION: u64 = 1;
+
+/// The project.json written for a freshly initialized project
+fn default_project_json(name: &str) -> Value {
+    json!({
+        "schema_version": SCHEMA_VERSION,
+        "name": name,
+        "version": "0.1.0",
+        "description": "New project",
+        "template": null,
+        "status": "active",
+        "completion": 0.0
+    })
+}
 
-        match status {
-            Ok(s) if s.success() => println!("✅ GitHub repo created and pushed!"),
-            Ok(s) => eprintln!("❌ Failed to create repo, exit code {}", s),
-            Err(e) => eprintln!("❌ Failed to run `gh`: {}", e),
+/// Bring a project.json value up to the current schema
+///
+/// Files written before `schema_version` existed count as version 0; any
+/// key `init` would have written (other than `name`) is backfilled with its
+/// default. Returns whether `data` was changed.
+pub fn ensure_schema(data: &mut Value) -> bool {
+    let Value::Object(map) = data else {
+        return false;
+    };
+    let version = map
+        .get("schema_version")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0);
+    if version >= SCHEMA_VERSION {
+        return false;
+    }
+
+    if let Value::Object(defaults) = default_project_json("") {
+        for (key, value) in defaults {
+            if key != "name" && !map.contains_key(&key) {
+                map.insert(key, value);
+            }
         }
     }
+    map.insert("schema_version".into(), json!(SCHEMA_VERSION));
+    true
+}
+
+/// Read a project.json and upgrade it to the current schema in memory
+pub fn read_project_json(path: &Path) -> Result<Value> {
+    let mut data = utils::read_json(path)?;
+    ensure_schema(&mut data);
+    Ok(data)
 }
 
 /// Initialize a new .proj folder and Git repo
-pub fn init_project(interactive: bool, template: Option<String>, vars: &[(String, String)]) {
-    let current_dir = env::current_dir().expect("Failed to get current directory");
+///
+/// In an existing project only the `vars` are applied, unless `--reinit`
+/// asks for the git, upstream and template steps to run again.
+pub fn init_project(args: &InitArgs) -> Result<()> {
+    let current_dir = env::current_dir().context("Failed to get current directory")?;
     let proj_name = current_dir
         .file_name()
         .unwrap_or_default()
@@ -101,65 +338,116 @@ pub fn init_project(interactive: bool, template: Option<String>, vars: &[(String
         .to_string();
 
     let proj_dir = current_dir.join(".proj");
+    let proj_file = proj_dir.join("project.json");
 
-    if !proj_dir.exists() {
-        fs::create_dir_all(&proj_dir).expect("Failed to create .proj directory");
+    if proj_dir.exists() && !args.reinit {
+        if !args.vars.is_empty() {
+            let mut json_data = read_project_json(&proj_file)?;
+            for (k, v) in &args.vars {
+                json_data[k] = Value::String(v.clone());
+            }
+            utils::write_json_atomic(&proj_file, &json_data)?;
+            info!("✅ Updated project '{}'", proj_name);
+        } else {
+            info!(".proj already exists; use --reinit to rerun setup");
+        }
+        return Ok(());
+    }
 
-        let default_json = json!({
-            "name": proj_name,
-            "version": "0.1.0",
-            "description": "New project",
-            "template": null,
-            "status": "active",
-            "completion": 0.0
-        });
+    let template_dirs = template::template_dirs(args.template_dir.as_deref());
+    let template_name = args.template_name();
 
-        fs::write(
-            proj_dir.join("project.json"),
-            serde_json::to_string_pretty(&default_json).unwrap(),
-        )
-        .expect("Failed to write project.json");
+    // Catch typos in the template name before anything is written
+    if let Some(t) = &template_name {
+        template::validate_template(t, &template_dirs)?;
+    }
+
+    // Check boilr up front so a missing binary doesn't leave a half-initialized project
+    let template_recorded = utils::read_json(&proj_file)?
+        .get("template")
+        .and_then(|v| v.as_str())
+        .is_some();
+    if !args.no_template
+        && !template_recorded
+        && (template_name.is_some() || !template::available_templates(&template_dirs).is_empty())
+    {
+        template::ensure_boilr()?;
+    }
+
+    let created_proj = !proj_dir.exists();
+    if created_proj {
+        fs::create_dir_all(&proj_dir)
+            .with_context(|| format!("Failed to create {}", proj_dir.display()))?;
+
+        let mut default_json = default_project_json(&proj_name);
 
-        println!("✅ Initialized project '{}'", proj_name);
+        // Initializing an existing codebase: pick up its README and version
+        if let Value::Object(detected) = detect_metadata(&current_dir) {
+            for (k, v) in detected {
+                default_json[k] = v;
+            }
+        }
+
+        if args.interactive {
+            for key in ["description", "version", "status"] {
+                let default = default_json[key].as_str().unwrap_or_default().to_string();
+                let label = format!("{}{}", key[..1].to_uppercase(), &key[1..]);
+                default_json[key] = json!(utils::prompt(&label, &default)?);
+            }
+        }
+
+        utils::write_json_atomic(&proj_file, &default_json)?;
+
+        info!("✅ Initialized project '{}'", proj_name);
     } else {
-        println!(".proj already exists.");
+        info!(".proj already exists.");
     }
 
-    init_git_repo(&current_dir);
-    maybe_create_upstream(&proj_name, &current_dir);
-    let proj_file = proj_dir.join("project.json");
-    let mut json_data = utils::read_json(&proj_file);
+    // The template runs before git and the upstream so a failure has only
+    // `.proj` to clean up
+    let mut json_data = read_project_json(&proj_file)?;
 
-    for (k, v) in vars {
+    for (k, v) in &args.vars {
         json_data[k] = Value::String(v.clone());
     }
 
-    if json_data.get("template").and_then(|v| v.as_str()).is_none() {
-        let chosen_template = template.or_else(template::select_template);
+    if !args.no_template && json_data.get("template").and_then(|v| v.as_str()).is_none() {
+        let chosen_template = template_name.or_else(|| template::select_template(&template_dirs));
         if let Some(t) = chosen_template {
-            template::apply_boilr_template(&t, &proj_file, interactive);
+            if let Err(e) = template::apply_boilr_template(
+                &t,
+                &proj_file,
+                &args.template_vars,
+                args.interactive,
+                &template_dirs,
+            ) {
+                if created_proj {
+                    let _ = fs::remove_dir_all(&proj_dir);
+                }
+                return Err(e);
+            }
             json_data["template"] = Value::String(t);
         }
     }
 
-    fs::write(
-        &proj_file,
-        serde_json::to_string_pretty(&json_data).unwrap(),
-    )
-    .expect("Failed to update project.json");
+    utils::write_json_atomic(&proj_file, &json_data)?;
+
+    init_git_repo(&current_dir);
+    maybe_create_upstream(&proj_name, &current_dir, args);
 
     // Link project in ~/projects if outside
-    if !current_dir.starts_with(projects_dir()) {
+    if !current_dir.starts_with(projects_dir()?) {
         link_in_projects_dir(&current_dir);
     }
     // After applying the Boilr template
-    if Command::new("git")
-        .arg("rev-parse")
-        .arg("--is-inside-work-tree")
-        .current_dir(&current_dir)
-        .output()
-        .map(|o| o.status.success())
-        .unwrap_or(false)
+    if args.git_push
+        && Command::new("git")
+            .arg("rev-parse")
+            .arg("--is-inside-work-tree")
+            .current_dir(&current_dir)
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
     {
         // Stage all files
         let _ = Command::new("git")
@@ -176,220 +464,1574 @@ pub fn init_project(interactive: bool, template: Option<String>, vars: &[(String
             .current_dir(&current_dir)
             .status();
 
-        // Push and set upstream
-        let _ = Command::new("git")
-            .arg("push")
-            .arg("--set-upstream")
-            .arg("origin")
-            .arg("master")
-            .current_dir(&current_dir)
-            .status();
+        // Push and set upstream on whatever branch git created
+        if let Some(branch) = current_branch(&current_dir) {
+            let _ = Command::new("git")
+                .arg("push")
+                .arg("--set-upstream")
+                .arg("origin")
+                .arg(branch)
+                .current_dir(&current_dir)
+                .status();
+        }
     }
+    Ok(())
 }
 
-/// Create a new project directory
-pub fn create_project(
+/// Apply a boilr template to the enclosing project after the fact and record
+/// it in project.json
+///
+/// A template that is already recorded is only replaced with `force`.
+pub fn apply_template(
     name: &str,
-    template: Option<String>,
-    vars: &[(String, String)],
+    force: bool,
     interactive: bool,
-) {
-    let path = Path::new(name)
-        .canonicalize()
-        .unwrap_or_else(|_| Path::new(name).to_path_buf());
+    template_dir: Option<&Path>,
+    template_vars: &[(String, String)],
+) -> Result<()> {
+    let proj_file = require_enclosing_project()?.join(".proj/project.json");
+    let data = read_project_json(&proj_file)?;
+
+    if let Some(current) = data.get("template").and_then(|v| v.as_str()) {
+        if !force {
+            return Err(errors::conflict(format!(
+                "Project already uses template '{}'; pass --force to apply '{}' anyway",
+                current, name
+            )));
+        }
+        eprintln!("⚠️  Replacing template '{}' with '{}'", current, name);
+    }
+
+    let template_dirs = template::template_dirs(template_dir);
+    template::validate_template(name, &template_dirs)?;
+    template::ensure_boilr()?;
+    template::apply_boilr_template(name, &proj_file, template_vars, interactive, &template_dirs)?;
+
+    // boilr may have rendered files into .proj, so read it again
+    let mut data = read_project_json(&proj_file)?;
+    data["template"] = json!(name);
+    utils::write_json_atomic(&proj_file, &data)?;
+    info!("✅ Recorded template '{}'", name);
+    Ok(())
+}
+
+/// Name of the checked-out branch, also for a repo without commits yet
+pub fn current_branch(path: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["symbolic-ref", "--short", "HEAD"])
+        .current_dir(path)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!branch.is_empty()).then_some(branch)
+}
+
+/// Where `create` puts a new project
+///
+/// A bare name goes to `~/projects/<name>`, anything containing a `/` is a
+/// path relative to the current directory (or absolute), and `--here`
+/// always creates the folder in the current directory.
+fn create_target(name: &str, here: bool) -> Result<PathBuf> {
+    let is_path = name.contains('/') || name.contains(std::path::MAIN_SEPARATOR);
+    Ok(if here || is_path {
+        env::current_dir()?.join(name)
+    } else {
+        projects_dir()?.join(name)
+    })
+}
+
+/// Create a new project directory
+pub fn create_project(name: &str, here: bool, args: &InitArgs) -> Result<()> {
+    let path = create_target(name, here)?;
     if path.exists() {
-        eprintln!("Error: directory '{}' already exists.", name);
-        return;
+        return Err(errors::conflict(format!(
+            "directory '{}' already exists.",
+            path.display()
+        )));
     }
 
-    fs::create_dir_all(&path).expect("Failed to create project directory");
-    env::set_current_dir(&path).expect("Failed to change directory");
+    if let Some(t) = &args.template_name() {
+        template::validate_template(t, &template::template_dirs(args.template_dir.as_deref()))?;
+    }
 
-    init_project(interactive, template, vars);
+    fs::create_dir_all(&path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let path = fs::canonicalize(&path)?;
+    let original_dir = env::current_dir()?;
+    env::set_current_dir(&path)?;
+
+    // Don't leave a half-initialized folder (or a link to it) behind
+    if let Err(e) = init_project(args) {
+        let _ = env::set_current_dir(&original_dir);
+        let link = projects_dir()?.join(path.file_name().unwrap_or_default());
+        if link.is_symlink() && fs::read_link(&link).is_ok_and(|target| target == path) {
+            let _ = fs::remove_file(&link);
+        }
+        let _ = fs::remove_dir_all(&path);
+        return Err(e);
+    }
 
     // Link in ~/projects if outside
-    if !path.starts_with(projects_dir()) {
+    if !path.starts_with(projects_dir()?) {
         link_in_projects_dir(&path);
     }
 
-    println!("📁 Created new project '{}'", name);
+    info!(
+        "📁 Created new project '{}' at {}",
+        path.file_name().unwrap_or_default().to_string_lossy(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Walk up from the current directory to the nearest folder containing `.proj/project.json`
+pub fn find_enclosing_project() -> Option<PathBuf> {
+    let current_dir = env::current_dir().ok()?;
+    current_dir
+        .ancestors()
+        .find(|dir| dir.join(".proj/project.json").is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Like `find_enclosing_project`, but errors when not inside a project
+pub fn require_enclosing_project() -> Result<PathBuf> {
+    find_enclosing_project().ok_or_else(|| {
+        errors::not_found(
+            "Not inside a project: no .proj/project.json found in any parent directory",
+        )
+    })
+}
+
+/// The project called `name`, or the one enclosing the current directory
+fn named_or_enclosing_project(name: Option<&str>) -> Result<PathBuf> {
+    match name {
+        Some(name) => find_project_path(name)
+            .ok_or_else(|| errors::not_found(format!("Project '{}' not found", name))),
+        None => require_enclosing_project(),
+    }
 }
 
-pub fn set_project_vars(vars: &[(String, String)]) {
-    let proj_file = Path::new(".proj/project.json");
-    let mut data = utils::read_json(proj_file);
+pub fn set_project_vars(vars: &[(String, String)], force_string: bool) -> Result<()> {
+    let proj_file = require_enclosing_project()?.join(".proj/project.json");
+    let mut data = read_project_json(&proj_file)?;
+    let mut completion = None;
 
     for (key, value) in vars {
-        if key == "completion" {
-            if let Ok(f) = value.parse::<f64>() {
-                data[key] = serde_json::json!(f);
-                continue;
-            }
+        if key == "completion"
+            && let Ok(f) = value.parse::<f64>()
+        {
+            utils::set_path(&mut data, key, serde_json::json!(f))?;
+            completion = Some(f);
+            continue;
+        }
+        // Versions like `1.0` must stay strings rather than becoming floats
+        let value = if force_string || key == "version" {
+            Value::String(value.clone())
+        } else {
+            utils::parse_value(value)
+        };
+        utils::set_path(&mut data, key, value)?;
+    }
+
+    // An explicit `status=` in the same command wins
+    if let Some(completion) = completion
+        && !vars.iter().any(|(key, _)| key == "status")
+        && config::load().auto_status_on_complete
+    {
+        let status = data.get("status").and_then(|v| v.as_str());
+        if completion >= 1.0 {
+            data["status"] = json!("done");
+        } else if status == Some("done") {
+            data["status"] = json!("active");
         }
-        data[key] = Value::String(value.clone());
     }
 
-    fs::write(proj_file, serde_json::to_string_pretty(&data).unwrap())
-        .expect("Failed to write project.json");
+    utils::write_json_atomic(&proj_file, &data)?;
 
-    println!("✅ Updated project.json");
+    info!("✅ Updated project.json");
+    Ok(())
 }
 
-pub fn get_project_var(key: &str) {
-    let proj_file = Path::new(".proj/project.json");
-    let data = utils::read_json(proj_file);
+/// Remove `keys` from the enclosing project's project.json, warning about
+/// any that aren't set
+pub fn unset_vars(keys: &[String]) -> Result<()> {
+    let proj_file = require_enclosing_project()?.join(".proj/project.json");
+    let mut data = read_project_json(&proj_file)?;
 
-    match data.get(key) {
-        Some(val) => println!("{}", val),
-        None => eprintln!("Key '{}' not found.", key),
+    let mut removed = 0;
+    for key in keys {
+        if utils::remove_path(&mut data, key).is_some() {
+            removed += 1;
+        } else {
+            eprintln!("⚠️  Key '{}' not found", key);
+        }
     }
-}
 
-pub fn init_git_repo(path: &Path) {
-    if path.join(".git").exists() {
-        return;
+    if removed > 0 {
+        utils::write_json_atomic(&proj_file, &data)?;
+        info!("✅ Updated project.json");
     }
-    let _ = Command::new("git").arg("init").current_dir(path).output();
+    Ok(())
 }
 
-pub fn scan_for_proj(recursive: bool) {
-    ensure_projects_dir().ok();
-
-    let mut seen = HashSet::new();
-
-    fn visit(dir: &Path, recursive: bool, seen: &mut HashSet<PathBuf>) {
-        if let Ok(entries) = fs::read_dir(dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.join(".proj").exists() {
-                    // Use canonical path to deduplicate symlinks
-                    if let Ok(real_path) = fs::canonicalize(&path) {
-                        if seen.insert(real_path) {
-                            println!(
-                                "Found project: {}",
-                                path.file_name().unwrap_or_default().to_string_lossy()
-                            );
-                        }
-                    }
-                }
+/// Set `status` on each named project, reporting failures and carrying on
+/// with the rest
+pub fn set_status(status: &str, names: &[String]) -> Result<()> {
+    let mut failed = 0;
 
-                if recursive && path.is_dir() {
-                    visit(&path, recursive, seen);
-                }
+    for name in names {
+        let result = find_project_path(name)
+            .ok_or_else(|| errors::not_found(format!("Project '{}' not found", name)))
+            .and_then(|path| {
+                let proj_file = path.join(".proj/project.json");
+                let mut data = read_project_json(&proj_file)?;
+                data["status"] = json!(status);
+                utils::write_json_atomic(&proj_file, &data)
+            });
+
+        match result {
+            Ok(()) => info!("✅ {}: {}", name, status),
+            Err(e) => {
+                eprintln!("❌ {}: {:#}", name, e);
+                failed += 1;
             }
         }
     }
 
-    // Scan current directory
-    visit(Path::new("."), recursive, &mut seen);
-
-    // Scan ~/projects/
-    visit(&projects_dir(), recursive, &mut seen);
+    if failed > 0 {
+        return Err(anyhow!(
+            "Failed to update {} of {} projects",
+            failed,
+            names.len()
+        ));
+    }
+    Ok(())
 }
 
-pub fn git_status_flags(path: &Path) -> (bool, bool, bool) {
-    use std::process::Command;
+/// Increment one component of the enclosing project's semver `version`,
+/// zeroing the lower ones and dropping any pre-release or build suffix
+pub fn bump_version(part: BumpPart) -> Result<()> {
+    let proj_file = require_enclosing_project()?.join(".proj/project.json");
+    let mut data = read_project_json(&proj_file)?;
 
-    // Untracked / unadded files
-    let unadded = Command::new("git")
-        .arg("ls-files")
-        .arg("--others")
-        .arg("--exclude-standard")
-        .current_dir(path)
-        .output()
-        .map(|o| !o.stdout.is_empty())
-        .unwrap_or(false);
+    let current = data
+        .get("version")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow!("project.json has no version to bump"))?
+        .to_string();
+    let old = semver::Version::parse(&current)
+        .with_context(|| format!("Version '{}' is not valid semver", current))?;
 
-    // Uncommitted changes (staged or unstaged)
-    let uncommitted = Command::new("git")
-        .arg("diff")
-        .arg("--quiet")
-        .current_dir(path)
-        .status()
-        .map(|s| !s.success())
-        .unwrap_or(false)
-        || Command::new("git")
-            .arg("diff")
-            .arg("--cached")
-            .arg("--quiet")
-            .current_dir(path)
-            .status()
-            .map(|s| !s.success())
-            .unwrap_or(false);
+    let new = match part {
+        BumpPart::Major => semver::Version::new(old.major + 1, 0, 0),
+        BumpPart::Minor => semver::Version::new(old.major, old.minor + 1, 0),
+        BumpPart::Patch => semver::Version::new(old.major, old.minor, old.patch + 1),
+    };
 
-    // Unpushed commits (only if remote exists)
-    let unpushed = Command::new("git")
-        .args(["rev-parse", "--abbrev-ref", "@{u}"])
-        .current_dir(path)
-        .output()
-        .map(|o| o.status.success()) // only run if upstream exists
-        .unwrap_or(false)
-        && Command::new("git")
-            .args(["log", "@{u}..HEAD", "--oneline"])
-            .current_dir(path)
-            .output()
-            .map(|o| !o.stdout.is_empty())
-            .unwrap_or(false);
+    data["version"] = json!(new.to_string());
+    utils::write_json_atomic(&proj_file, &data)?;
 
-    (unadded, uncommitted, unpushed)
+    println!("{} → {}", old, new);
+    Ok(())
 }
 
-pub fn list_projects(status_filter: &str, show_progress: bool) {
-    ensure_projects_dir().ok();
+/// Tag the enclosing project's git repo with `v<version>` from project.json,
+/// optionally pushing the tag to origin
+pub fn tag_version(push: bool) -> Result<()> {
+    let root = require_enclosing_project()?;
+    let data = read_project_json(&root.join(".proj/project.json"))?;
+    let version = data
+        .get("version")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow!("project.json has no version to tag"))?;
+    let tag = format!("v{}", version);
+
+    let exists = Command::new("git")
+        .args(["rev-parse", "--quiet", "--verify"])
+        .arg(format!("refs/tags/{}", tag))
+        .current_dir(&root)
+        .output()
+        .context("Failed to run `git rev-parse`")?
+        .status
+        .success();
+    if exists {
+        return Err(errors::conflict(format!("Tag '{}' already exists", tag)));
+    }
 
-    let mut seen = std::collections::HashSet::new();
+    let status = Command::new("git")
+        .args(["tag", "-a", &tag, "-m"])
+        .arg(format!("Version {}", version))
+        .current_dir(&root)
+        .status()
+        .context("Failed to run `git tag`")?;
+    if !status.success() {
+        anyhow::bail!("git tag failed with exit code {:?}", status.code());
+    }
+    info!("🏷️  Tagged {}", tag);
 
-    /// Recursively scan directories for projects
-    fn visit(
-        dir: &Path,
-        recursive: bool,
-        seen: &mut std::collections::HashSet<PathBuf>,
-    ) -> Vec<PathBuf> {
-        let mut projects = Vec::new();
+    if push {
+        let status = Command::new("git")
+            .args(["push", "origin", &tag])
+            .current_dir(&root)
+            .status()
+            .context("Failed to run `git push`")?;
+        if !status.success() {
+            anyhow::bail!("git push failed with exit code {:?}", status.code());
+        }
+        info!("🚀 Pushed {} to origin", tag);
+    }
+    Ok(())
+}
 
-        if let Ok(entries) = fs::read_dir(dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
+/// Stage everything and commit with `message` in every git project that has
+/// uncommitted changes, reporting the result per project
+pub fn commit_all(message: &str, dry_run: bool) -> Result<()> {
+    if message.trim().is_empty() {
+        return Err(anyhow!("Refusing to commit with an empty message"));
+    }
 
-                // Skip non-directories
-                if !path.is_dir() {
-                    continue;
-                }
+    let changed: Vec<PathBuf> = collect_projects()
+        .into_iter()
+        .filter(|path| path.join(".git").exists() && git_status_flags(path).1)
+        .collect();
 
-                // Skip hidden folders
-                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                    if name.starts_with('.') {
-                        continue;
-                    }
-                }
+    if changed.is_empty() {
+        info!("No projects with uncommitted changes");
+        return Ok(());
+    }
 
-                let proj_file = path.join(".proj/project.json");
-                if proj_file.is_file() {
-                    if let Ok(real_path) = fs::canonicalize(&path) {
-                        if seen.insert(real_path.clone()) {
-                            projects.push(real_path);
-                        }
+    let (mut committed, mut failed) = (0, 0);
+    for path in &changed {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if dry_run {
+            println!("Would commit '{}'", name);
+            committed += 1;
+            continue;
+        }
+
+        let result = Command::new("git")
+            .args(["add", "-A"])
+            .current_dir(path)
+            .output()
+            .and_then(|output| {
+                if !output.status.success() {
+                    return Ok(output);
+                }
+                Command::new("git")
+                    .args(["commit", "-q", "-m", message])
+                    .current_dir(path)
+                    .output()
+            });
+
+        match result {
+            Ok(output) if output.status.success() => {
+                info!("✅ {}: committed", name);
+                committed += 1;
+            }
+            Ok(output) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let reason = stderr.lines().find(|l| !l.trim().is_empty());
+                eprintln!("❌ {}: {}", name, reason.unwrap_or("git commit failed"));
+                failed += 1;
+            }
+            Err(e) => {
+                eprintln!("❌ {}: Failed to run git: {}", name, e);
+                failed += 1;
+            }
+        }
+    }
+
+    if !dry_run {
+        info!(
+            "📝 Committed {} project{}",
+            committed,
+            if committed == 1 { "" } else { "s" }
+        );
+    }
+
+    if failed > 0 {
+        return Err(anyhow!(
+            "Failed to commit {} of {} projects",
+            failed,
+            changed.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Push every git project that is ahead of its upstream, optionally only
+/// those with `status`, reporting the result per project
+pub fn push_all(status: Option<&str>, dry_run: bool) -> Result<()> {
+    let mut ahead = Vec::new();
+    for path in collect_projects() {
+        if !path.join(".git").exists() {
+            continue;
+        }
+        if let Some(status) = status {
+            match read_project_json(&path.join(".proj/project.json")) {
+                Ok(data)
+                    if data
+                        .get("status")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("active")
+                        == status => {}
+                Ok(_) => continue,
+                Err(e) => {
+                    eprintln!("❌ {:#}", e);
+                    continue;
+                }
+            }
+        }
+        // Only set when an upstream exists
+        let (_, _, unpushed) = git_status_flags(&path);
+        if unpushed {
+            ahead.push(path);
+        }
+    }
+
+    if ahead.is_empty() {
+        info!("No projects with unpushed commits");
+        return Ok(());
+    }
+
+    let (mut pushed, mut failed) = (0, 0);
+    for path in &ahead {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if dry_run {
+            println!("Would push '{}'", name);
+            pushed += 1;
+            continue;
+        }
+
+        match Command::new("git").arg("push").current_dir(path).output() {
+            Ok(output) if output.status.success() => {
+                info!("✅ {}: pushed", name);
+                pushed += 1;
+            }
+            Ok(output) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let reason = stderr.lines().find(|l| !l.trim().is_empty());
+                eprintln!("❌ {}: {}", name, reason.unwrap_or("git push failed"));
+                failed += 1;
+            }
+            Err(e) => {
+                eprintln!("❌ {}: Failed to run `git push`: {}", name, e);
+                failed += 1;
+            }
+        }
+    }
+
+    if !dry_run {
+        info!(
+            "🚀 Pushed {} project{}",
+            pushed,
+            if pushed == 1 { "" } else { "s" }
+        );
+    }
+
+    if failed > 0 {
+        return Err(anyhow!(
+            "Failed to push {} of {} projects",
+            failed,
+            ahead.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Append a timestamped note to `.proj/log.jsonl`, or print the log
+/// newest-first when no message is given
+pub fn log(message: Option<&str>) -> Result<()> {
+    let log_file = require_enclosing_project()?.join(".proj/log.jsonl");
+
+    let Some(message) = message else {
+        let content = match fs::read_to_string(&log_file) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                info!("No log entries yet");
+                return Ok(());
+            }
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read {}", log_file.display()));
+            }
+        };
+
+        let lines: Vec<&str> = content.lines().collect();
+        for (i, line) in lines.iter().enumerate().rev() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry: Value = serde_json::from_str(line).with_context(|| {
+                format!(
+                    "Invalid log entry on line {} of {}",
+                    i + 1,
+                    log_file.display()
+                )
+            })?;
+            let timestamp = entry
+                .get("timestamp")
+                .and_then(|v| v.as_str())
+                .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+                .map(|t| {
+                    t.with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string()
+                })
+                .unwrap_or_else(|| "?".to_string());
+            let message = entry.get("message").and_then(|v| v.as_str()).unwrap_or("");
+            println!("{}  {}", timestamp, message);
+        }
+        return Ok(());
+    };
+
+    let entry = json!({
+        "timestamp": chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+        "message": message,
+    });
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_file)
+        .with_context(|| format!("Failed to open {}", log_file.display()))?;
+    writeln!(file, "{}", entry)?;
+
+    info!("📝 Logged");
+    Ok(())
+}
+
+/// Print a project variable, the whole project.json without a key, or its
+/// top-level keys
+///
+/// A missing key is an error unless a `default` is given to print instead.
+pub fn get_project_var(key: Option<&str>, keys_only: bool, default: Option<&str>) -> Result<()> {
+    let proj_file = require_enclosing_project()?.join(".proj/project.json");
+    let data = read_project_json(&proj_file)?;
+
+    let Some(key) = key else {
+        if keys_only {
+            if let Some(obj) = data.as_object() {
+                for k in obj.keys() {
+                    println!("{}", k);
+                }
+            }
+        } else {
+            println!("{}", serde_json::to_string_pretty(&data)?);
+        }
+        return Ok(());
+    };
+
+    match (utils::get_path(&data, key), default) {
+        // Strings print raw, like the default, so they work in shell scripts
+        (Some(Value::String(val)), _) => println!("{}", val),
+        (Some(val), _) => println!("{}", val),
+        (None, Some(default)) => println!("{}", default),
+        (None, None) => return Err(errors::not_found(format!("Key '{}' not found", key))),
+    }
+    Ok(())
+}
+
+/// Run `scripts.<script>` from project.json in the project root through
+/// `sh`, appending `args` to the command
+///
+/// `$SHELL` isn't used: shells such as fish have no `"$@"` to forward args.
+pub fn run_script(script: &str, args: &[String]) -> Result<()> {
+    let root = require_enclosing_project()?;
+    let data = read_project_json(&root.join(".proj/project.json"))?;
+    let scripts = data.get("scripts").and_then(|v| v.as_object());
+
+    let Some(command) = scripts.and_then(|s| s.get(script)).and_then(|v| v.as_str()) else {
+        let available: Vec<&str> = scripts
+            .map(|s| s.keys().map(String::as_str).collect())
+            .unwrap_or_default();
+        return Err(errors::not_found(if available.is_empty() {
+            format!("Script '{}' not found: project.json has no scripts", script)
+        } else {
+            format!(
+                "Script '{}' not found. Available: {}",
+                script,
+                available.join(", ")
+            )
+        }));
+    };
+
+    info!("▶️  {}", command);
+
+    // `"$@"` forwards the extra args without re-splitting them; the `sh`
+    // after the command fills `$0` so the args start at `$1`
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$@\"", command))
+        .arg("sh")
+        .args(args)
+        .current_dir(&root)
+        .status()
+        .context("Failed to run `sh`")?;
+
+    if !status.success() {
+        anyhow::bail!(
+            "Script '{}' failed with exit code {:?}",
+            script,
+            status.code()
+        );
+    }
+    Ok(())
+}
+
+/// Print `export KEY='value'` lines for a project's `.env` file followed by
+/// the `env` object in its project.json, so the latter wins when eval'd
+pub fn print_env(name: Option<&str>) -> Result<()> {
+    let project_path = named_or_enclosing_project(name)?;
+
+    let mut vars: Vec<(String, String)> = Vec::new();
+
+    let env_file = project_path.join(".env");
+    match fs::read_to_string(&env_file) {
+        Ok(content) => {
+            for line in content.lines().map(str::trim) {
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                let line = line.strip_prefix("export ").unwrap_or(line);
+                let Some((key, value)) = line.split_once('=') else {
+                    continue;
+                };
+                let value = value.trim();
+                let value = ['"', '\'']
+                    .iter()
+                    .find_map(|q| value.strip_prefix(*q)?.strip_suffix(*q))
+                    .unwrap_or(value);
+                vars.push((key.trim().to_string(), value.to_string()));
+            }
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", env_file.display()));
+        }
+    }
+
+    let data = read_project_json(&project_path.join(".proj/project.json"))?;
+    if let Some(env) = data.get("env").and_then(|v| v.as_object()) {
+        for (key, value) in env {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            vars.push((key.clone(), value));
+        }
+    }
+
+    for (key, value) in vars {
+        let valid = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            eprintln!("⚠️  Skipping invalid variable name '{}'", key);
+            continue;
+        }
+        println!("export {}='{}'", key, value.replace('\'', "'\\''"));
+    }
+    Ok(())
+}
+
+/// Open a project (or the enclosing one) in the user's editor
+pub fn open_project(name: Option<&str>) -> Result<()> {
+    let project_path = named_or_enclosing_project(name)?;
+
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .ok()
+        .or_else(|| config::load().editor)
+        .filter(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| "code".to_string());
+
+    // Editors may carry arguments, e.g. `code --wait`
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("code");
+
+    let status = Command::new(program)
+        .args(parts)
+        .arg(&project_path)
+        .current_dir(&project_path)
+        .status()
+        .map_err(|e| anyhow!("Failed to run editor '{}': {}", program, e))?;
+
+    if !status.success() {
+        anyhow::bail!("Editor '{}' exited with {}", program, status);
+    }
+    Ok(())
+}
+
+/// Turn a git remote URL into the repository's web page, e.g.
+/// `git@github.com:me/repo.git` → `https://github.com/me/repo`
+fn remote_web_url(remote: &str) -> String {
+    let remote = remote.trim();
+    let url = if let Some(rest) = remote.strip_prefix("ssh://") {
+        // ssh://git@host:2222/owner/repo.git
+        let rest = rest.split_once('@').map_or(rest, |(_, r)| r);
+        let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+        let host = host.split_once(':').map_or(host, |(h, _)| h);
+        format!("https://{}/{}", host, path)
+    } else if let Some((user_host, path)) = remote.split_once(':')
+        && !remote.contains("://")
+    {
+        // git@host:owner/repo.git
+        let host = user_host.split_once('@').map_or(user_host, |(_, h)| h);
+        format!("https://{}/{}", host, path)
+    } else if let Some((scheme, rest)) = remote.split_once("://") {
+        // https://user@host/owner/repo.git: drop any credentials
+        let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+        let host = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+        format!("{}://{}/{}", scheme, host, path)
+    } else {
+        remote.to_string()
+    };
+
+    url.trim_end_matches('/')
+        .trim_end_matches(".git")
+        .to_string()
+}
+
+/// Open the web page of a project's `origin` remote in the browser
+pub fn open_url(name: Option<&str>) -> Result<()> {
+    let project_path = named_or_enclosing_project(name)?;
+
+    let output = Command::new("git")
+        .args(["remote", "get-url", "origin"])
+        .current_dir(&project_path)
+        .output()
+        .context("Failed to run `git remote get-url`")?;
+    if !output.status.success() {
+        return Err(errors::not_found(format!(
+            "No origin remote in {}",
+            project_path.display()
+        )));
+    }
+    let url = remote_web_url(&String::from_utf8_lossy(&output.stdout));
+
+    let mut opener = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", "start", ""]);
+        cmd
+    } else {
+        Command::new("xdg-open")
+    };
+
+    info!("🌐 Opening {}", url);
+    let status = opener
+        .arg(&url)
+        .status()
+        .with_context(|| format!("Failed to open {}", url))?;
+    if !status.success() {
+        anyhow::bail!("Opening {} failed with {}", url, status);
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "git2"))]
+pub fn init_git_repo(path: &Path) {
+    if path.join(".git").exists() {
+        return;
+    }
+    let _ = Command::new("git").arg("init").current_dir(path).output();
+}
+
+#[cfg(feature = "git2")]
+pub fn init_git_repo(path: &Path) {
+    if path.join(".git").exists() {
+        return;
+    }
+    if let Err(e) = git2::Repository::init(path) {
+        eprintln!("Failed to initialize git repository: {}", e);
+    }
+}
+
+pub fn scan_for_proj(
+    recursive: bool,
+    max_depth: Option<usize>,
+    register: bool,
+    as_json: bool,
+) -> Result<()> {
+    ensure_projects_dir().ok();
+
+    let mut seen = HashSet::new();
+    let recursive = recursive || max_depth.is_some();
+
+    fn visit(
+        dir: &Path,
+        depth: usize,
+        recursive: bool,
+        max_depth: Option<usize>,
+        as_json: bool,
+        seen: &mut HashSet<PathBuf>,
+        found: &mut Vec<PathBuf>,
+    ) {
+        if let Ok(entries) = fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if !path.is_dir() || should_skip_dir(&path) {
+                    continue;
+                }
+                // Use canonical paths so symlinks and overlapping roots are
+                // only walked once
+                let Some(real_path) = first_visit(&path, seen) else {
+                    continue;
+                };
+
+                if path.join(".proj").exists() {
+                    let name = path.file_name().unwrap_or_default().to_string_lossy();
+                    if as_json {
+                        let line = json!({ "name": name, "path": real_path });
+                        println!("{}", line);
+                    } else {
+                        println!("Found project: {}", name);
                     }
-                } else if recursive {
-                    projects.extend(visit(&path, recursive, seen));
+                    found.push(real_path);
                 }
+
+                if recursive && max_depth.is_none_or(|max| depth < max) {
+                    visit(&path, depth + 1, recursive, max_depth, as_json, seen, found);
+                }
+            }
+        }
+    }
+
+    let mut found = Vec::new();
+
+    // Scan current directory
+    visit(
+        Path::new("."),
+        1,
+        recursive,
+        max_depth,
+        as_json,
+        &mut seen,
+        &mut found,
+    );
+
+    // Scan ~/projects/
+    if let Ok(dir) = projects_dir() {
+        visit(
+            &dir, 1, recursive, max_depth, as_json, &mut seen, &mut found,
+        );
+    }
+
+    if register {
+        register_projects(&found)?;
+    }
+    Ok(())
+}
+
+/// Symlink each project into `~/projects` unless it is already reachable there
+fn register_projects(projects: &[PathBuf]) -> Result<()> {
+    let projects_dir = projects_dir()?;
+    let projects_root = fs::canonicalize(&projects_dir).unwrap_or_else(|_| projects_dir.clone());
+    let (mut registered, mut known) = (0, 0);
+
+    for real_path in projects {
+        let Some(name) = real_path.file_name() else {
+            continue;
+        };
+        let link = projects_dir.join(name);
+
+        if real_path.starts_with(&projects_root) {
+            known += 1;
+        } else if link.symlink_metadata().is_ok() {
+            if fs::canonicalize(&link).ok().as_ref() == Some(real_path) {
+                known += 1;
+            } else {
+                eprintln!(
+                    "⚠️  Not registering '{}': '{}' already points elsewhere",
+                    real_path.display(),
+                    link.display()
+                );
+            }
+        } else {
+            link_in_projects_dir(real_path);
+            info!("🔗 Registered '{}'", real_path.display());
+            registered += 1;
+        }
+    }
+
+    info!(
+        "✅ Registered {} new project(s), {} already known",
+        registered, known
+    );
+    Ok(())
+}
+
+/// Git flags for a project: (unadded, uncommitted, unpushed)
+pub fn git_status_flags(path: &Path) -> (bool, bool, bool) {
+    let (unadded, uncommitted) = git_worktree_flags(path);
+    (unadded, uncommitted, git_unpushed(path))
+}
+
+#[cfg(feature = "git2")]
+fn git_worktree_flags(path: &Path) -> (bool, bool) {
+    use git2::{Repository, Status, StatusOptions};
+
+    let Ok(repo) = Repository::open(path) else {
+        return (false, false);
+    };
+
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(true).exclude_submodules(true);
+
+    let (mut unadded, mut uncommitted) = (false, false);
+    if let Ok(statuses) = repo.statuses(Some(&mut opts)) {
+        for entry in statuses.iter() {
+            let status = entry.status();
+            // Untracked / unadded files
+            unadded |= status.contains(Status::WT_NEW);
+            // Uncommitted changes (staged or unstaged)
+            uncommitted |= status.intersects(
+                Status::INDEX_NEW
+                    | Status::INDEX_MODIFIED
+                    | Status::INDEX_DELETED
+                    | Status::INDEX_RENAMED
+                    | Status::INDEX_TYPECHANGE
+                    | Status::WT_MODIFIED
+                    | Status::WT_DELETED
+                    | Status::WT_RENAMED
+                    | Status::WT_TYPECHANGE,
+            );
+        }
+    }
+
+    (unadded, uncommitted)
+}
+
+/// Whether HEAD has commits its upstream doesn't (false without an upstream)
+#[cfg(feature = "git2")]
+fn git_unpushed(path: &Path) -> bool {
+    use git2::{Branch, Repository};
+
+    (|| {
+        let repo = Repository::open(path).ok()?;
+        let head = repo.head().ok()?;
+        let local = head.target()?;
+        let upstream = Branch::wrap(head).upstream().ok()?.get().target()?;
+        let (ahead, _) = repo.graph_ahead_behind(local, upstream).ok()?;
+        Some(ahead > 0)
+    })()
+    .unwrap_or(false)
+}
+
+#[cfg(not(feature = "git2"))]
+fn git_worktree_flags(path: &Path) -> (bool, bool) {
+    use std::process::Command;
+
+    // Untracked / unadded files
+    let unadded = Command::new("git")
+        .arg("ls-files")
+        .arg("--others")
+        .arg("--exclude-standard")
+        .current_dir(path)
+        .output()
+        .map(|o| !o.stdout.is_empty())
+        .unwrap_or(false);
+
+    // Uncommitted changes (staged or unstaged)
+    let uncommitted = Command::new("git")
+        .arg("diff")
+        .arg("--quiet")
+        .current_dir(path)
+        .status()
+        .map(|s| !s.success())
+        .unwrap_or(false)
+        || Command::new("git")
+            .arg("diff")
+            .arg("--cached")
+            .arg("--quiet")
+            .current_dir(path)
+            .status()
+            .map(|s| !s.success())
+            .unwrap_or(false);
+
+    (unadded, uncommitted)
+}
+
+/// Whether HEAD has commits its upstream doesn't (false without an upstream)
+#[cfg(not(feature = "git2"))]
+fn git_unpushed(path: &Path) -> bool {
+    use std::process::Command;
+
+    Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", "@{u}"])
+        .current_dir(path)
+        .output()
+        .map(|o| o.status.success()) // only run if upstream exists
+        .unwrap_or(false)
+        && Command::new("git")
+            .args(["log", "@{u}..HEAD", "--oneline"])
+            .current_dir(path)
+            .output()
+            .map(|o| !o.stdout.is_empty())
+            .unwrap_or(false)
+}
+
+/// Resolve the commit HEAD points at by reading `.git` directly (no subprocess)
+fn read_git_head(path: &Path) -> Option<String> {
+    let git_dir = path.join(".git");
+    let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let head = head.trim();
+
+    let Some(reference) = head.strip_prefix("ref: ") else {
+        return Some(head.to_string()); // detached HEAD
+    };
+
+    if let Ok(commit) = fs::read_to_string(git_dir.join(reference)) {
+        return Some(commit.trim().to_string());
+    }
+
+    // Not a loose ref: look it up in packed-refs
+    let packed = fs::read_to_string(git_dir.join("packed-refs")).ok()?;
+    packed
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .find(|(_, name)| *name == reference)
+        .map(|(commit, _)| commit.to_string())
+}
+
+/// Newest mtime among the files that move when HEAD or an upstream ref does
+///
+/// Loose refs are rewritten through a rename, which bumps the mtime of the
+/// directory holding them, so each remote's ref directory is checked too.
+fn newest_ref_mtime(path: &Path) -> Option<std::time::SystemTime> {
+    let git_dir = path.join(".git");
+    let mut candidates = vec![
+        git_dir.join("HEAD"),
+        git_dir.join("FETCH_HEAD"),
+        git_dir.join("packed-refs"),
+        git_dir.join("config"),
+        git_dir.join("refs/heads"),
+        git_dir.join("refs/remotes"),
+    ];
+    if let Ok(remotes) = fs::read_dir(git_dir.join("refs/remotes")) {
+        candidates.extend(remotes.flatten().map(|e| e.path()));
+    }
+
+    candidates
+        .iter()
+        .filter_map(|p| fs::metadata(p).and_then(|m| m.modified()).ok())
+        .max()
+}
+
+/// Keep the status cache out of `git status` via `.git/info/exclude`
+fn exclude_status_cache(path: &Path) {
+    const ENTRY: &str = ".proj/.status-cache.json";
+    let exclude_file = path.join(".git/info/exclude");
+    let existing = fs::read_to_string(&exclude_file).unwrap_or_default();
+    if existing.lines().any(|line| line.trim() == ENTRY) {
+        return;
+    }
+
+    let mut content = existing;
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(ENTRY);
+    content.push('\n');
+
+    if let Some(parent) = exclude_file.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let _ = fs::write(&exclude_file, content);
+}
+
+/// `git_status_flags`, reusing the unpushed check from `.proj/.status-cache.json`
+///
+/// Only the unpushed flag is cached, keyed on HEAD and the ref mtimes; the
+/// working tree flags are always checked live so edits anywhere show up.
+pub fn cached_git_status_flags(path: &Path, use_cache: bool) -> (bool, bool, bool) {
+    let cache_file = path.join(".proj/.status-cache.json");
+    let head = read_git_head(path);
+    let (unadded, uncommitted) = git_worktree_flags(path);
+
+    if use_cache
+        && let Some(head) = &head
+        && let Ok(cache_mtime) = fs::metadata(&cache_file).and_then(|m| m.modified())
+        && newest_ref_mtime(path).is_some_and(|newest| newest <= cache_mtime)
+    {
+        // A corrupt cache is just a miss
+        let cache = utils::read_json(&cache_file).unwrap_or_default();
+        if cache.get("head").and_then(|v| v.as_str()) == Some(head.as_str())
+            && let Some(unpushed) = cache.get("unpushed").and_then(|v| v.as_bool())
+        {
+            return (unadded, uncommitted, unpushed);
+        }
+    }
+
+    if head.is_some() {
+        exclude_status_cache(path);
+    }
+
+    let unpushed = git_unpushed(path);
+
+    if let Some(head) = head {
+        let cache = json!({
+            "head": head,
+            "unpushed": unpushed,
+        });
+        // Caching is best-effort, a failed write just means a fresh check next time
+        let _ = fs::write(&cache_file, serde_json::to_string_pretty(&cache).unwrap());
+    }
+
+    (unadded, uncommitted, unpushed)
+}
+
+/// Render the git flags (`+` unadded, `c` uncommitted, `^` unpushed) for a project
+pub fn git_flags_string(project_path: &Path, use_cache: bool) -> String {
+    // Git flags only if .git exists
+    let (unadded, uncommitted, unpushed) = if project_path.join(".git").exists() {
+        cached_git_status_flags(project_path, use_cache)
+    } else {
+        (false, false, false)
+    };
+
+    let mut flags = String::new();
+    if unadded {
+        flags.push_str(&output::paint("+", "31"));
+    }
+    if uncommitted {
+        flags.push_str(&output::paint("c", "31"));
+    }
+    if unpushed {
+        flags.push_str(&output::paint("^", "31"));
+    }
+    flags
+}
+
+/// Compute `git_flags_string` for many projects concurrently, keeping input order
+pub fn git_flags_parallel(paths: &[PathBuf], use_cache: bool) -> Vec<String> {
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4);
+    let chunk_size = paths.len().div_ceil(workers).max(1);
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = paths
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|path| git_flags_string(path, use_cache))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("git status thread panicked"))
+            .collect()
+    })
+}
+
+/// Narrowest and widest bar `list -p` picks when fitting the terminal
+const MIN_BAR_WIDTH: usize = 10;
+const MAX_BAR_WIDTH: usize = 60;
+
+/// Fit the progress bar to the terminal, leaving `reserved` columns for the
+/// rest of the line; 20 columns when stdout isn't a terminal
+fn auto_bar_width(reserved: usize) -> usize {
+    match terminal_size::terminal_size() {
+        Some((terminal_size::Width(cols), _)) => (cols as usize)
+            .saturating_sub(reserved)
+            .clamp(MIN_BAR_WIDTH, MAX_BAR_WIDTH),
+        None => 20,
+    }
+}
+
+/// Render a colored progress bar for a completion value between 0.0 and 1.0
+pub fn progress_bar(completion: f64, bar_len: usize) -> String {
+    // Completion above 1.0 (or float noise at 100%) must not overflow the bar
+    let filled = ((completion * bar_len as f64).round() as usize).min(bar_len);
+    let empty = bar_len.saturating_sub(filled);
+
+    let color = if completion < 0.33 {
+        "31" // red
+    } else if completion < 0.66 {
+        "33" // yellow
+    } else {
+        "32" // green
+    };
+
+    format!(
+        "{}{}",
+        output::paint(&"█".repeat(filled), color),
+        "░".repeat(empty)
+    )
+}
+
+/// Colors for common statuses when the config doesn't set one
+const DEFAULT_STATUS_COLORS: &[(&str, &str)] = &[
+    ("active", "green"),
+    ("paused", "yellow"),
+    ("done", "blue"),
+    ("blocked", "red"),
+];
+
+/// ANSI code for a color name such as `green` or `bright-blue`
+fn ansi_color(name: &str) -> Option<&'static str> {
+    Some(match name.to_ascii_lowercase().as_str() {
+        "black" => "30",
+        "red" => "31",
+        "green" => "32",
+        "yellow" => "33",
+        "blue" => "34",
+        "magenta" => "35",
+        "cyan" => "36",
+        "white" => "37",
+        "bright-black" | "gray" | "grey" => "90",
+        "bright-red" => "91",
+        "bright-green" => "92",
+        "bright-yellow" => "93",
+        "bright-blue" => "94",
+        "bright-magenta" => "95",
+        "bright-cyan" => "96",
+        "bright-white" => "97",
+        _ => return None,
+    })
+}
+
+/// ANSI code for `status`: `status_colors` from the config, else the
+/// built-in default, else none
+fn status_color(status: &str, configured: &BTreeMap<String, String>) -> Option<&'static str> {
+    configured
+        .get(status)
+        .and_then(|name| ansi_color(name))
+        .or_else(|| {
+            DEFAULT_STATUS_COLORS
+                .iter()
+                .find(|(s, _)| *s == status)
+                .and_then(|(_, name)| ansi_color(name))
+        })
+}
+
+/// Print the metadata, progress and git flags of a single project
+pub fn print_project_details(project_path: &Path, data: &Value) {
+    let field = |key: &str, default: &'static str| -> String {
+        data.get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or(default)
+            .to_string()
+    };
+    let completion = data
+        .get("completion")
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.0);
+
+    println!(
+        "{} {}",
+        field("name", "unnamed"),
+        git_flags_string(project_path, false)
+    );
+    println!("  path:        {}", project_path.display());
+    println!("  version:     {}", field("version", "-"));
+    println!("  description: {}", field("description", "-"));
+    println!("  status:      {}", field("status", "active"));
+    println!(
+        "  completion:  [{}] {:.0}%{}",
+        progress_bar(completion, 20),
+        completion * 100.0,
+        todo::count_note(project_path)
+    );
+
+    // Any other keys, e.g. the template or values added with `set`
+    if let Value::Object(map) = data {
+        let shown = [
+            "name",
+            "version",
+            "description",
+            "status",
+            "completion",
+            "schema_version",
+        ];
+        for (key, value) in map.iter().filter(|(k, _)| !shown.contains(&k.as_str())) {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                Value::Null => "-".to_string(),
+                other => other.to_string(),
+            };
+            println!("  {:<12} {}", format!("{}:", key), value);
+        }
+    }
+}
+
+/// Show the details of the project in the current directory
+pub fn show_status() -> Result<()> {
+    let project_path = require_enclosing_project()?;
+    let data = read_project_json(&project_path.join(".proj/project.json"))?;
+    print_project_details(&project_path, &data);
+    Ok(())
+}
+
+/// Show the details of a project by name, from any directory
+pub fn show_info(name: &str) -> Result<()> {
+    let project_path = find_project_path(name)
+        .ok_or_else(|| errors::not_found(format!("Project '{}' not found", name)))?;
+    let project_path = fs::canonicalize(&project_path).unwrap_or(project_path);
+    let data = read_project_json(&project_path.join(".proj/project.json"))?;
+    print_project_details(&project_path, &data);
+    Ok(())
+}
+
+/// Print just the canonical path of a project by name
+///
+/// A missing project exits with 1 like `which(1)` rather than the not-found
+/// code other lookups use.
+pub fn which_project(name: &str) -> Result<()> {
+    let project_path =
+        find_project_path(name).ok_or_else(|| anyhow!("Project '{}' not found", name))?;
+    let real_path = fs::canonicalize(&project_path)
+        .with_context(|| format!("Failed to resolve {}", project_path.display()))?;
+    println!("{}", real_path.display());
+    Ok(())
+}
+
+/// Unix time of the last commit in a git project
+#[cfg(feature = "git2")]
+fn last_commit_time(path: &Path) -> Option<i64> {
+    let repo = git2::Repository::open(path).ok()?;
+    let commit = repo.head().ok()?.peel_to_commit().ok()?;
+    Some(commit.time().seconds())
+}
+
+/// Unix time of the last commit in a git project
+#[cfg(not(feature = "git2"))]
+fn last_commit_time(path: &Path) -> Option<i64> {
+    let output = Command::new("git")
+        .args(["log", "-1", "--format=%ct"])
+        .current_dir(path)
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+/// When a project was last worked on: its last git commit, or the newest
+/// file mtime for projects without commits
+pub fn last_activity(path: &Path) -> Option<std::time::SystemTime> {
+    match last_commit_time(path) {
+        Some(secs) => {
+            Some(std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs.max(0) as u64))
+        }
+        None => walkdir::WalkDir::new(path)
+            .into_iter()
+            // Hidden files such as .proj/project.json count, git internals don't
+            .filter_entry(|e| {
+                e.depth() == 0
+                    || e.file_name()
+                        .to_str()
+                        .is_none_or(|n| n != ".git" && !SKIP_DIRS.contains(&n))
+            })
+            .flatten()
+            .filter(|e| e.file_type().is_file())
+            .filter_map(|e| e.metadata().ok()?.modified().ok())
+            .max(),
+    }
+}
+
+/// Time elapsed since `time`, zero if it lies in the future
+fn elapsed_since(time: std::time::SystemTime) -> std::time::Duration {
+    std::time::SystemTime::now()
+        .duration_since(time)
+        .unwrap_or_default()
+}
+
+/// Whole days since a project was last worked on
+pub fn idle_days(path: &Path) -> Option<u64> {
+    Some(elapsed_since(last_activity(path)?).as_secs() / 86_400)
+}
+
+/// Format a duration as a short age such as `5m ago` or `3d ago`
+fn format_age(age: std::time::Duration) -> String {
+    let secs = age.as_secs();
+    match secs {
+        0..60 => "just now".to_string(),
+        60..3_600 => format!("{}m ago", secs / 60),
+        3_600..86_400 => format!("{}h ago", secs / 3_600),
+        _ => format!("{}d ago", secs / 86_400),
+    }
+}
+
+/// List the projects with the most recent activity first
+pub fn recent_projects(limit: usize) {
+    ensure_projects_dir().ok();
+
+    let mut projects: Vec<(PathBuf, std::time::SystemTime)> = collect_projects()
+        .into_iter()
+        .filter_map(|path| {
+            let active = last_activity(&path)?;
+            Some((path, active))
+        })
+        .collect();
+    projects.sort_by_key(|(_, active)| std::cmp::Reverse(*active));
+    projects.truncate(limit);
+
+    let names: Vec<String> = projects
+        .iter()
+        .map(|(path, _)| {
+            path.file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string()
+        })
+        .collect();
+    let width = names.iter().map(|n| n.len()).max().unwrap_or(0);
+
+    for (name, (path, active)) in names.iter().zip(&projects) {
+        println!(
+            "{:<width$}  {:>9}  {}",
+            name,
+            format_age(elapsed_since(*active)),
+            path.display(),
+            width = width
+        );
+    }
+}
+
+/// Name to show for a project: the `name` from project.json, falling back to
+/// the folder name, with the folder added when the two differ
+fn display_name(path: &Path, data: &Value) -> String {
+    let dir = path.file_name().unwrap_or_default().to_string_lossy();
+    match data.get("name").and_then(|v| v.as_str()) {
+        Some(name) if !name.is_empty() && name != dir => format!("{} (dir: {})", name, dir),
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => dir.into_owned(),
+    }
+}
+
+pub fn list_projects(args: &ListArgs) {
+    let config = config::load();
+    let status_filter = args
+        .status
+        .clone()
+        .or(config.default_list_status)
+        .unwrap_or_else(|| "active".to_string());
+    let statuses: Vec<&str> = status_filter
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect();
+    let any_status = statuses.is_empty() || statuses.contains(&"all");
+    let show_progress = args.progress;
+    let use_cache = !args.no_cache;
+
+    ensure_projects_dir().ok();
+
+    // Read and filter first so git is only queried for the projects shown
+    let mut projects = Vec::new();
+    for project_path in collect_projects() {
+        let proj_file = project_path.join(".proj/project.json");
+        if !proj_file.is_file() {
+            continue; // safety check
+        }
+
+        let data = match read_project_json(&proj_file) {
+            Ok(data) => data,
+            Err(e) => {
+                eprintln!("❌ {:#}", e);
+                continue;
             }
+        };
+
+        let status = data
+            .get("status")
+            .and_then(|v| v.as_str())
+            .unwrap_or("active");
+
+        if !any_status && !statuses.contains(&status) {
+            continue;
+        }
+
+        if let Some(template) = &args.template
+            && data.get("template").and_then(|v| v.as_str()) != Some(template.as_str())
+        {
+            continue;
+        }
+
+        if let Some(glob) = &args.filter
+            && !project_path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| utils::glob_match(glob, n))
+        {
+            continue;
+        }
+
+        // Only worth computing when filtering on it
+        let idle = args.stale.and_then(|_| idle_days(&project_path));
+        if let Some(min_days) = args.stale
+            && idle.is_none_or(|days| days < min_days)
+        {
+            continue;
+        }
+
+        projects.push((project_path, data, idle));
+    }
+
+    // Git is only queried for the flags shown or filtered on
+    let paths: Vec<PathBuf> = projects.iter().map(|(path, _, _)| path.clone()).collect();
+    let all_flags = if args.path && !args.dirty {
+        vec![String::new(); paths.len()]
+    } else {
+        git_flags_parallel(&paths, use_cache)
+    };
+    let (projects, all_flags): (Vec<_>, Vec<_>) = projects
+        .into_iter()
+        .zip(all_flags)
+        .filter(|(_, flags)| !args.dirty || !flags.is_empty())
+        .unzip();
+
+    if args.path {
+        for (project_path, _, _) in &projects {
+            println!("{}", project_path.display());
         }
-
-        projects
+        return;
     }
 
-    // Scan current directory and ~/projects
-    let mut all_projects = visit(Path::new("."), true, &mut seen);
-    all_projects.extend(visit(&projects_dir(), true, &mut seen));
+    let paths: Vec<PathBuf> = projects.iter().map(|(path, _, _)| path.clone()).collect();
+    let todo_notes: Vec<String> = paths.iter().map(|path| todo::count_note(path)).collect();
+
+    let mut status_counts: Vec<(String, usize)> = Vec::new();
+    let mut total_completion = 0.0;
+
+    let bar_len = match args.bar_width {
+        Some(width) => width as usize,
+        None if show_progress => {
+            let longest_name = projects
+                .iter()
+                .map(|(path, data, _)| display_name(path, data).chars().count())
+                .max()
+                .unwrap_or(0);
+            // Name, up to four flags, " [", "] 100%" and an idle suffix
+            let age = if args.stale.is_some() { 14 } else { 0 };
+            let todos = todo_notes.iter().map(|n| n.chars().count()).max();
+            auto_bar_width(longest_name + 5 + 2 + 6 + age + todos.unwrap_or(0))
+        }
+        None => 0,
+    };
 
-    for project_path in all_projects {
-        let proj_file = project_path.join(".proj/project.json");
-        if !proj_file.is_file() {
-            continue; // safety check
+    let status_colors = config.status_colors;
+    for name in status_colors.values() {
+        if ansi_color(name).is_none() {
+            eprintln!("⚠️  Unknown color '{}' in status_colors", name);
         }
+    }
 
-        let data = utils::read_json(&proj_file);
+    for (((project_path, data, idle), mut flags), todos) in
+        projects.iter().zip(all_flags).zip(&todo_notes)
+    {
+        // A hand-edited completion that isn't a number would otherwise just show 0%
+        if data.get("completion").is_some_and(|c| !c.is_number()) {
+            flags.push_str(&output::paint("!", "33"));
+        }
 
         let status = data
             .get("status")
@@ -400,75 +2042,80 @@ pub fn list_projects(status_filter: &str, show_progress: bool) {
             .and_then(|v| v.as_f64())
             .unwrap_or(0.0);
 
-        if status_filter != "all" && status != status_filter {
-            continue;
+        match status_counts.iter_mut().find(|(s, _)| s == status) {
+            Some((_, count)) => *count += 1,
+            None => status_counts.push((status.to_string(), 1)),
         }
+        total_completion += completion;
 
-        // Git flags only if .git exists
-        let (unadded, uncommitted, unpushed) = if project_path.join(".git").exists() {
-            git_status_flags(&project_path)
-        } else {
-            (false, false, false)
+        let color = status_color(status, &status_colors);
+        let paint = |text: &str| match color {
+            Some(code) => output::paint(text, code),
+            None => text.to_string(),
         };
-
-        let mut flags = String::new();
-        if unadded {
-            flags.push_str("\x1b[31m+\x1b[0m");
-        } // Use \x1b for escape sequences
-        if uncommitted {
-            flags.push_str("\x1b[31mc\x1b[0m");
-        }
-        if unpushed {
-            flags.push_str("\x1b[31m^\x1b[0m");
-        }
-
-        let project_name = project_path
-            .file_name()
-            .unwrap_or_default()
-            .to_string_lossy();
+        let project_name = paint(&display_name(project_path, data));
+        let age = idle
+            .map(|days| format!(" · idle {}d", days))
+            .unwrap_or_default();
 
         if show_progress {
-            let bar_len = 20;
-            let filled = (completion * bar_len as f64).round() as usize;
-            let empty = bar_len - filled;
-
-            let color = if completion < 0.33 {
-                "\x1b[31m" // red
-            } else if completion < 0.66 {
-                "\x1b[33m" // yellow
-            } else {
-                "\x1b[32m" // green
-            };
-
-            let bar = format!(
-                "{}{}{}\x1b[0m{}",
-                color,
-                "█".repeat(filled),
-                "\x1b[0m",
-                "░".repeat(empty)
-            );
+            let bar = progress_bar(completion, bar_len);
 
             println!(
-                "{} {} [{}] {:.0}%",
+                "{} {} [{}] {:.0}%{}{}",
                 project_name,
                 flags,
                 bar,
-                completion * 100.0
+                completion * 100.0,
+                todos,
+                age
             );
         } else {
             println!(
-                "{} {} (status: {}, completion: {:.0}%)",
+                "{} {} (status: {}, completion: {:.0}%{}{})",
                 project_name,
                 flags,
-                status,
-                completion * 100.0
+                paint(status),
+                completion * 100.0,
+                todos,
+                age
             );
         }
     }
+
+    if args.summary {
+        // Most common status first, ties in order of first appearance
+        status_counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        let per_status: Vec<String> = status_counts
+            .iter()
+            .map(|(status, count)| format!("{} {}", count, status))
+            .collect();
+        let average = if projects.is_empty() {
+            0.0
+        } else {
+            total_completion / projects.len() as f64
+        };
+
+        let mut footer = format!(
+            "{} project{}",
+            projects.len(),
+            if projects.len() == 1 { "" } else { "s" }
+        );
+        if !per_status.is_empty() {
+            footer.push_str(&format!(" · {}", per_status.join(", ")));
+        }
+        footer.push_str(&format!(" · avg {:.0}%", average * 100.0));
+        println!("{}", footer);
+    }
 }
 
-pub fn migrate_project(name: &str, destination: Option<PathBuf>) -> Result<()> {
-    let default_dest = dirs::home_dir().unwrap().join("projects");
+pub fn migrate_project(
+    name: &str,
+    destination: Option<PathBuf>,
+    copy: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let default_dest = projects_dir()?;
     let destination = destination.unwrap_or(default_dest);
     let dest_path = destination.join(name);
 
@@ -483,89 +2130,561 @@ pub fn migrate_project(name: &str, destination: Option<PathBuf>) -> Result<()> {
                 None
             }
         })
-        .ok_or_else(|| anyhow!("Project '{}' not found", name))?;
+        .ok_or_else(|| errors::not_found(format!("Project '{}' not found", name)))?;
 
     let real_path = fs::canonicalize(&project_path)?;
 
     if dest_path.exists() {
-        anyhow::bail!("Destination already contains a project named '{}'", name);
+        return Err(errors::conflict(format!(
+            "Destination already contains a project named '{}'",
+            name
+        )));
+    }
+
+    if dry_run {
+        println!(
+            "Would {} '{}' to '{}'",
+            if copy { "copy" } else { "move" },
+            real_path.display(),
+            dest_path.display()
+        );
+        if project_path.is_symlink() {
+            println!("Would remove symlink '{}'", project_path.display());
+        }
+        return Ok(());
     }
 
     fs::create_dir_all(&destination)?;
-    fs::rename(&real_path, &dest_path)?;
+
+    if copy {
+        fs_extra::dir::copy(
+            &real_path,
+            &dest_path,
+            &fs_extra::dir::CopyOptions::new().copy_inside(true),
+        )?;
+        let dest_path = fs::canonicalize(&dest_path)?;
+
+        // Point ~/projects at the copy, the original stays where it was
+        if project_path.is_symlink() {
+            fs::remove_file(&project_path)?;
+        }
+        if !dest_path.starts_with(projects_dir()?) {
+            link_in_projects_dir(&dest_path);
+        }
+
+        info!("✅ Project '{}' copied to '{}'", name, dest_path.display());
+
+        // Nothing was moved, so an older record must not be undone now
+        clear_last_migrate()?;
+        return Ok(());
+    }
+
+    // Falls back to copy-then-delete when the destination is on another filesystem
+    utils::move_dir(&real_path, &dest_path).map_err(|e| {
+        anyhow!(
+            "Failed to move '{}' to '{}': {} (try --copy)",
+            real_path.display(),
+            dest_path.display(),
+            e
+        )
+    })?;
+    // Stored for --undo, which may run from any directory
+    let dest_path = fs::canonicalize(&dest_path)?;
 
     // Remove old symlink if it exists
-    if project_path.exists() && project_path.is_symlink() {
+    let linked = project_path.is_symlink();
+    if linked {
         fs::remove_file(&project_path)?;
     }
+    if !dest_path.starts_with(projects_dir()?) {
+        link_in_projects_dir(&dest_path);
+    }
 
-    println!(
+    info!(
         "✅ Project '{}' migrated to '{}'",
         name,
         dest_path.display()
     );
+
+    let record = json!({
+        "source": real_path,
+        "destination": dest_path,
+        "linked": linked,
+    });
+    let record_path = last_migrate_path()?;
+    let written = fs::create_dir_all(record_path.parent().unwrap_or(Path::new(".")))
+        .map_err(anyhow::Error::from)
+        .and_then(|()| utils::write_json_atomic(&record_path, &record));
+    if let Err(e) = written {
+        eprintln!("⚠️  Couldn't record the migrate for --undo: {:#}", e);
+    }
+    Ok(())
+}
+
+/// Where the last move done by `migrate` is recorded for `migrate --undo`
+fn last_migrate_path() -> Result<PathBuf> {
+    Ok(utils::home_dir()?.join(".proj/last-migrate.json"))
+}
+
+/// Forget the recorded migrate, if there is one
+fn clear_last_migrate() -> Result<()> {
+    let record_path = last_migrate_path()?;
+    match fs::remove_file(&record_path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => {
+            Err(e).with_context(|| format!("Failed to clear {}", record_path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Move the last migrated project back and restore its ~/projects symlink,
+/// then clear the record so the undo can't be repeated
+pub fn undo_migrate(dry_run: bool) -> Result<()> {
+    let record_path = last_migrate_path()?;
+    let record = utils::read_json(&record_path)?;
+    let field = |key: &str| record.get(key).and_then(|v| v.as_str()).map(PathBuf::from);
+    let (Some(source), Some(destination)) = (field("source"), field("destination")) else {
+        return Err(errors::not_found("No migrate to undo"));
+    };
+    let linked = record
+        .get("linked")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    if !destination.exists() {
+        return Err(errors::not_found(format!(
+            "Migrated project '{}' no longer exists",
+            destination.display()
+        )));
+    }
+    // A project migrated out of ~/projects leaves migrate's own link behind at
+    // its old path; that link is ours to replace, anything else is not
+    let own_link = fs::read_link(&source).is_ok_and(|target| target == destination);
+    if !own_link && fs::symlink_metadata(&source).is_ok() {
+        return Err(errors::conflict(format!(
+            "Original location '{}' is in use again",
+            source.display()
+        )));
+    }
+
+    if dry_run {
+        println!(
+            "Would move '{}' back to '{}'",
+            destination.display(),
+            source.display()
+        );
+        return Ok(());
+    }
+
+    if own_link {
+        fs::remove_file(&source)?;
+    }
+    if let Some(parent) = source.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    utils::move_dir(&destination, &source).with_context(|| {
+        format!(
+            "Failed to move '{}' back to '{}'",
+            destination.display(),
+            source.display()
+        )
+    })?;
+
+    // Drop the link migrate made to the destination before restoring the original
+    let dest_link = projects_dir()?.join(destination.file_name().unwrap_or_default());
+    if fs::read_link(&dest_link).is_ok_and(|target| target == destination) {
+        fs::remove_file(&dest_link)?;
+    }
+    if linked && !source.starts_with(projects_dir()?) {
+        link_in_projects_dir(&source);
+    }
+    clear_last_migrate()?;
+
+    info!("↩️  Moved project back to '{}'", source.display());
+    Ok(())
+}
+
+pub fn rename_project(old: &str, new: &str) -> Result<()> {
+    let project_path = find_project_path(old)
+        .ok_or_else(|| errors::not_found(format!("Project '{}' not found", old)))?;
+    if find_project_path(new).is_some() {
+        return Err(errors::conflict(format!(
+            "A project named '{}' already exists",
+            new
+        )));
+    }
+
+    let real_path = fs::canonicalize(&project_path)?;
+    let parent = real_path
+        .parent()
+        .ok_or_else(|| anyhow!("Project '{}' has no parent directory", old))?;
+    let new_path = parent.join(new);
+    let new_link = projects_dir()?.join(new);
+
+    if new_path.exists() {
+        return Err(errors::conflict(format!(
+            "Destination '{}' already exists",
+            new_path.display()
+        )));
+    }
+    if new_link.symlink_metadata().is_ok() {
+        return Err(errors::conflict(format!(
+            "'{}' already exists",
+            new_link.display()
+        )));
+    }
+
+    // Load project.json first so a corrupt file aborts before anything moves
+    let mut data = read_project_json(&real_path.join(".proj/project.json"))?;
+    data["name"] = Value::String(new.to_string());
+
+    fs::rename(&real_path, &new_path)?;
+
+    let proj_file = new_path.join(".proj/project.json");
+    utils::write_json_atomic(&proj_file, &data)?;
+
+    // Replace the old symlink in ~/projects with one under the new name
+    let old_link = projects_dir()?.join(old);
+    if old_link.is_symlink() {
+        fs::remove_file(&old_link)?;
+    }
+    if !new_path.starts_with(projects_dir()?) {
+        link_in_projects_dir(&new_path);
+    }
+
+    info!("✅ Renamed project '{}' to '{}'", old, new);
+    Ok(())
+}
+
+/// Report broken symlinks and unhealthy project.json files under `~/projects`
+///
+/// With `fix`, dead symlinks are removed and missing keys are backfilled
+/// with the defaults `init` would have written.
+pub fn doctor(fix: bool) -> Result<()> {
+    let projects = projects_dir()?;
+    let mut problems = 0;
+
+    if let Ok(entries) = fs::read_dir(&projects) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_link = path.symlink_metadata().is_ok_and(|m| m.is_symlink());
+
+            if is_link && !path.exists() {
+                problems += 1;
+                let target = fs::read_link(&path).unwrap_or_default();
+                println!(
+                    "❌ Broken symlink: {} → {}",
+                    path.display(),
+                    target.display()
+                );
+                if fix {
+                    fs::remove_file(&path)
+                        .with_context(|| format!("Failed to remove {}", path.display()))?;
+                    println!("   🔧 Removed dead symlink");
+                }
+                continue;
+            }
+
+            // Registered projects must have a project.json; plain folders may
+            // just be groups of nested projects
+            if path.is_dir()
+                && (is_link || path.join(".proj").is_dir())
+                && !path.join(".proj/project.json").is_file()
+            {
+                problems += 1;
+                println!("❌ Missing .proj/project.json: {}", path.display());
+            }
+        }
+    }
+
+    for project_path in visit_projects(&projects, true, &mut HashSet::new()) {
+        let proj_file = project_path.join(".proj/project.json");
+        let name = project_path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+
+        let mut data = match utils::read_json(&proj_file) {
+            Ok(Value::Object(map)) => Value::Object(map),
+            Ok(_) => {
+                problems += 1;
+                println!("❌ {}: project.json is not a JSON object", name);
+                continue;
+            }
+            Err(e) => {
+                problems += 1;
+                println!("❌ {}: {:#}", name, e);
+                continue;
+            }
+        };
+
+        if let Some(completion) = data.get("completion") {
+            match completion.as_f64() {
+                None => {
+                    problems += 1;
+                    println!("❌ {}: completion {} is not a number", name, completion);
+                }
+                Some(c) if !(0.0..=1.0).contains(&c) => {
+                    problems += 1;
+                    println!("❌ {}: completion {} is outside 0.0-1.0", name, completion);
+                }
+                Some(_) => {}
+            }
+        }
+
+        let Value::Object(defaults) = default_project_json(&name) else {
+            unreachable!()
+        };
+        let missing: Vec<String> = defaults
+            .keys()
+            .filter(|k| data.get(k.as_str()).is_none())
+            .cloned()
+            .collect();
+
+        if !missing.is_empty() {
+            problems += 1;
+            println!("❌ {}: missing keys {}", name, missing.join(", "));
+            if fix {
+                for key in &missing {
+                    data[key] = defaults[key].clone();
+                }
+                utils::write_json_atomic(&proj_file, &data)?;
+                println!("   🔧 Backfilled default values");
+            }
+        }
+    }
+
+    if problems == 0 {
+        println!("✅ No problems found");
+    } else if fix {
+        println!(
+            "⚠️  Found {} problem(s), fixed what could be fixed",
+            problems
+        );
+    } else {
+        println!(
+            "⚠️  Found {} problem(s), run `project doctor --fix` to repair",
+            problems
+        );
+    }
+
     Ok(())
 }
 
-pub fn remove_project(name: &str, force: bool) -> anyhow::Result<()> {
-    use anyhow::{Context, anyhow};
+/// Return the trash directory (`~/.proj/trash`)
+pub fn trash_dir() -> Result<PathBuf> {
+    Ok(utils::home_dir()?.join(".proj/trash"))
+}
+
+pub fn remove_project(name: &str, force: bool, purge: bool, dry_run: bool) -> anyhow::Result<()> {
+    use anyhow::Context;
     use std::io::{self, Write};
 
-    let projects_dir = dirs::home_dir().unwrap().join("projects");
+    let projects_dir = projects_dir()?;
     let symlink_path = projects_dir.join(name);
 
     // Determine actual project path
-    let project_path =
-        find_project_path(name).ok_or_else(|| anyhow!("Project '{}' not found", name))?;
+    let project_path = find_project_path(name)
+        .ok_or_else(|| errors::not_found(format!("Project '{}' not found", name)))?;
+    let real_path = fs::canonicalize(&project_path)?;
+
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
+    let trash_path = trash_dir()?.join(format!("{}_{}", name, timestamp));
+
+    if dry_run {
+        if purge {
+            println!("Would delete '{}'", real_path.display());
+        } else {
+            println!(
+                "Would move '{}' to '{}'",
+                real_path.display(),
+                trash_path.display()
+            );
+        }
+        if symlink_path.is_symlink() {
+            println!("Would remove symlink '{}'", symlink_path.display());
+        }
+        return Ok(());
+    }
 
     if !force {
+        let action = if purge {
+            "permanently remove"
+        } else {
+            "move to trash"
+        };
         print!(
-            "⚠️  Are you sure you want to permanently remove '{}' ? [y/N]: ",
-            name
+            "⚠️  This will {} '{}'. Type the project name to confirm: ",
+            action, name
         );
         io::stdout().flush()?;
         let mut input = String::new();
         io::stdin().read_line(&mut input)?;
-        if !matches!(input.trim().to_lowercase().as_str(), "y" | "yes") {
-            println!("❎ Aborted removal of '{}'", name);
+        if input.trim() != name {
+            println!("❎ Name did not match, aborted removal of '{}'", name);
             return Ok(());
         }
     }
 
-    // If the project is **inside ~/projects/**, just delete it
-    if project_path.starts_with(&projects_dir) {
-        fs::remove_dir_all(&project_path)
-            .with_context(|| format!("Failed to delete project '{}'", project_path.display()))?;
+    if purge {
+        fs::remove_dir_all(&real_path)
+            .with_context(|| format!("Failed to delete project '{}'", real_path.display()))?;
     } else {
-        // Project is outside ~/projects, delete actual path
-        fs::remove_dir_all(&project_path)
-            .with_context(|| format!("Failed to delete project '{}'", project_path.display()))?;
+        fs::create_dir_all(trash_dir()?)?;
+        utils::move_dir(&real_path, &trash_path).with_context(|| {
+            format!("Failed to move project '{}' to trash", real_path.display())
+        })?;
+        info!("♻️  Moved '{}' to '{}'", name, trash_path.display());
+    }
 
-        // Then remove symlink in ~/projects/ if it exists
-        if symlink_path.exists() {
-            fs::remove_file(&symlink_path).or_else(|_| fs::remove_dir_all(&symlink_path))?;
-            println!("🔗 Removed symlink '{}'", symlink_path.display());
-        }
+    // Then remove symlink in ~/projects/ if it exists
+    if symlink_path.is_symlink() {
+        fs::remove_file(&symlink_path)?;
+        info!("🔗 Removed symlink '{}'", symlink_path.display());
     }
 
-    println!("🗑️  Project '{}' removed successfully", name);
+    info!("🗑️  Project '{}' removed successfully", name);
     Ok(())
 }
 
-pub fn clone_project(source: &str, dest: Option<&str>, git_clone: bool) -> anyhow::Result<()> {
-    use anyhow::{Context, anyhow};
-    use serde_json::json;
+/// How many directory levels `detect_metadata` searches for `info.py` and
+/// `VERSION` files
+const METADATA_SEARCH_DEPTH: usize = 3;
+
+/// Detect a description and version for an existing directory
+///
+/// The description comes from the first lines of a README, the version from
+/// the latest git tag, the Cargo.toml `[package]` (or `[workspace.package]`)
+/// version, the package.json `version`, an `info.py` `__version__`, or a
+/// `VERSION` file, in that order. Only the fields that were found are present
+/// in the result.
+pub fn detect_metadata(path: &Path) -> Value {
     use walkdir::WalkDir;
 
+    let mut metadata = json!({});
+
+    // Description from README
+    for name in &["README.md", "README.mkd", "README"] {
+        let readme_path = path.join(name);
+        if readme_path.exists()
+            && let Ok(content) = fs::read_to_string(readme_path)
+        {
+            metadata["description"] = json!(content.lines().take(3).collect::<Vec<_>>().join(" "));
+            break;
+        }
+    }
+
+    let mut version = None;
+
+    // Try latest Git tag if git repo
+    if path.join(".git").exists()
+        && let Ok(output) = Command::new("git")
+            .arg("describe")
+            .arg("--tags")
+            .arg("--abbrev=0")
+            .current_dir(path)
+            .output()
+        && output.status.success()
+    {
+        let ver = String::from_utf8_lossy(&output.stdout);
+        version = Some(ver.trim().to_string());
+    }
+
+    // Top-level manifests: Cargo.toml `[package] version` (or the workspace
+    // version it inherits), then package.json `"version"`
+    if version.is_none()
+        && let Ok(content) = fs::read_to_string(path.join("Cargo.toml"))
+        && let Ok(manifest) = toml::from_str::<toml::Table>(&content)
+    {
+        let package_version = |section: &toml::Value| {
+            section
+                .get("package")
+                .and_then(|p| p.get("version"))
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        };
+        let manifest = toml::Value::Table(manifest);
+        version = package_version(&manifest)
+            .or_else(|| manifest.get("workspace").and_then(package_version));
+    }
+
+    if version.is_none()
+        && let Ok(package) = utils::read_json(&path.join("package.json"))
+    {
+        version = package
+            .get("version")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+    }
+
+    // Search a few levels down, skipping build output, dependencies and
+    // hidden directories such as .git
+    let walk = || {
+        WalkDir::new(path)
+            .max_depth(METADATA_SEARCH_DEPTH)
+            .into_iter()
+            .filter_entry(|e| {
+                e.depth() == 0 || !(e.file_type().is_dir() && should_skip_dir(e.path()))
+            })
+            .flatten()
+    };
+
+    // Check info.py recursively
+    if version.is_none()
+        && let Some(info_path) = walk().find(|e| e.file_name() == "info.py")
+        && let Ok(content) = fs::read_to_string(info_path.path())
+    {
+        for line in content.lines() {
+            if let Some(ver) = line.strip_prefix("__version__")
+                && let Some(ver) = ver.split('=').nth(1)
+            {
+                version = Some(
+                    ver.trim_matches(|c: char| c == '\'' || c == '"' || c.is_whitespace())
+                        .to_string(),
+                );
+                break;
+            }
+        }
+    }
+
+    // Check VERSION file recursively
+    if version.is_none() {
+        for entry in walk() {
+            if entry
+                .file_name()
+                .to_string_lossy()
+                .eq_ignore_ascii_case("VERSION")
+                && let Ok(ver) = fs::read_to_string(entry.path())
+            {
+                version = Some(ver.trim().to_string());
+                break;
+            }
+        }
+    }
+
+    if let Some(version) = version {
+        metadata["version"] = json!(version);
+    }
+
+    metadata
+}
+
+pub fn clone_project(args: &CloneArgs) -> anyhow::Result<()> {
+    use anyhow::Context;
+    use serde_json::json;
+
+    let source = args.source.as_str();
+
     // --- Resolve destination path ---
-    let dest_path: PathBuf = if let Some(d) = dest {
+    let mut dest_path: PathBuf = if let Some(d) = &args.dest {
         let path = PathBuf::from(d);
 
         if path.as_os_str() == "." || path.as_os_str() == "./" {
             // Current directory: create folder with source project name
             let name = source
                 .split('/')
-                .last()
+                .next_back()
                 .unwrap_or("cloned_project")
                 .trim_end_matches(".git");
             Path::new(".").join(name)
@@ -573,26 +2692,58 @@ pub fn clone_project(source: &str, dest: Option<&str>, git_clone: bool) -> anyho
             // Absolute path: always append project name
             let name = source
                 .split('/')
-                .last()
+                .next_back()
                 .unwrap_or("cloned_project")
                 .trim_end_matches(".git");
             path.join(name)
         } else {
             // Relative name inside ~/projects
-            projects_dir().join(path)
+            projects_dir()?.join(path)
         }
     } else {
         // No dest → default to ~/projects/<source_name>
         let name = source
             .split('/')
-            .last()
+            .next_back()
             .unwrap_or("cloned_project")
             .trim_end_matches(".git");
-        projects_dir().join(name)
+        projects_dir()?.join(name)
     };
 
+    if args.auto_rename && dest_path.symlink_metadata().is_ok() {
+        let base = dest_path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        let mut n = 1;
+        while dest_path
+            .with_file_name(format!("{}-{}", base, n))
+            .symlink_metadata()
+            .is_ok()
+        {
+            n += 1;
+        }
+        dest_path = dest_path.with_file_name(format!("{}-{}", base, n));
+        info!(
+            "📛 '{}' exists, cloning as '{}'",
+            base,
+            dest_path.file_name().unwrap().to_string_lossy()
+        );
+    }
+
     if dest_path.exists() {
-        anyhow::bail!("Destination '{}' already exists", dest_path.display());
+        return Err(errors::conflict(format!(
+            "Destination '{}' already exists",
+            dest_path.display()
+        )));
+    }
+
+    // Check the template before cloning so a typo doesn't leave a clone behind
+    let template_dirs = template::template_dirs(None);
+    if let Some(t) = &args.template {
+        template::validate_template(t, &template_dirs)?;
+        template::ensure_boilr()?;
     }
 
     fs::create_dir_all(dest_path.parent().unwrap()).with_context(|| {
@@ -603,16 +2754,23 @@ pub fn clone_project(source: &str, dest: Option<&str>, git_clone: bool) -> anyho
     })?;
 
     // --- Determine if source is a Git URL ---
-    if source.starts_with("http://") || source.starts_with("https://") || source.starts_with("git@")
-    {
-        println!(
+    let is_git_url = source.starts_with("http://")
+        || source.starts_with("https://")
+        || source.starts_with("git@");
+
+    if is_git_url {
+        info!(
             "🌐 Cloning repository '{}' into '{}'",
             source,
             dest_path.display()
         );
 
-        let status = Command::new("git")
-            .arg("clone")
+        let mut git = Command::new("git");
+        git.arg("clone");
+        if let Some(depth) = args.depth {
+            git.arg("--depth").arg(depth.to_string());
+        }
+        let status = git
             .arg(source)
             .arg(&dest_path)
             .status()
@@ -622,14 +2780,14 @@ pub fn clone_project(source: &str, dest: Option<&str>, git_clone: bool) -> anyho
             anyhow::bail!("Git clone failed with exit code {:?}", status.code());
         }
 
-        println!("✅ Repository cloned successfully");
+        info!("✅ Repository cloned successfully");
     } else {
         // Local project
         let source_path = find_project_path(source)
-            .ok_or_else(|| anyhow!("Source project '{}' not found", source))?;
+            .ok_or_else(|| errors::not_found(format!("Source project '{}' not found", source)))?;
 
-        if git_clone && source_path.join(".git").exists() {
-            println!(
+        if args.git_clone && source_path.join(".git").exists() {
+            info!(
                 "🌱 Cloning local Git repository '{}' into '{}'",
                 source_path.display(),
                 dest_path.display()
@@ -646,7 +2804,7 @@ pub fn clone_project(source: &str, dest: Option<&str>, git_clone: bool) -> anyho
                 anyhow::bail!("Git clone failed with exit code {:?}", status.code());
             }
         } else {
-            println!(
+            info!(
                 "📁 Copying project '{}' into '{}'",
                 source_path.display(),
                 dest_path.display()
@@ -663,7 +2821,16 @@ pub fn clone_project(source: &str, dest: Option<&str>, git_clone: bool) -> anyho
 
     // --- Generate .proj/project.json if missing ---
     let proj_file = dest_path.join(".proj/project.json");
-    if !proj_file.exists() {
+    if proj_file.exists() {
+        // A copy of a local project is a new project: keep its description,
+        // version and template but not the source's name or progress
+        if !is_git_url && !args.preserve {
+            let mut data = read_project_json(&proj_file)?;
+            data["name"] = json!(dest_path.file_name().unwrap_or_default().to_string_lossy());
+            data["completion"] = json!(0.0);
+            utils::write_json_atomic(&proj_file, &data)?;
+        }
+    } else {
         fs::create_dir_all(proj_file.parent().unwrap())?;
 
         let project_name = dest_path
@@ -672,111 +2839,54 @@ pub fn clone_project(source: &str, dest: Option<&str>, git_clone: bool) -> anyho
             .to_string_lossy()
             .to_string();
 
-        // Template = git URL if cloned
-        let template = if source.starts_with("http://")
-            || source.starts_with("https://")
-            || source.starts_with("git@")
-        {
-            Some(source.to_string())
-        } else {
-            None
-        };
-
-        // Description from README
-        let mut description = String::new();
-        for name in &["README.md", "README.mkd", "README"] {
-            let readme_path = dest_path.join(name);
-            if readme_path.exists() {
-                if let Ok(content) = fs::read_to_string(readme_path) {
-                    description = content.lines().take(3).collect::<Vec<_>>().join(" ");
-                    break;
-                }
-            }
-        }
-
-        let mut version = "0.0.1".to_string();
-
-        // Try latest Git tag if git repo
-        if dest_path.join(".git").exists() {
-            if let Ok(output) = Command::new("git")
-                .arg("describe")
-                .arg("--tags")
-                .arg("--abbrev=0")
-                .current_dir(&dest_path)
-                .output()
-            {
-                if output.status.success() {
-                    let ver = String::from_utf8_lossy(&output.stdout);
-                    version = ver.trim().to_string();
-                }
-            }
-        }
-
-        // Check info.py recursively
-        fn find_info_py(path: &Path) -> Option<std::path::PathBuf> {
-            for entry in WalkDir::new(path).into_iter().flatten() {
-                if entry.file_name() == "info.py" {
-                    return Some(entry.path().to_path_buf());
-                }
-            }
-            None
-        }
-
-        if version == "0.0.1" {
-            if let Some(info_path) = find_info_py(&dest_path) {
-                if let Ok(content) = fs::read_to_string(&info_path) {
-                    for line in content.lines() {
-                        if let Some(ver) = line.strip_prefix("__version__") {
-                            if let Some(ver) = ver.split('=').nth(1) {
-                                version = ver
-                                    .trim_matches(|c: char| {
-                                        c == '\'' || c == '"' || c.is_whitespace()
-                                    })
-                                    .to_string();
-                                break;
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        // Check VERSION file recursively
-        if version == "0.0.1" {
-            for entry in WalkDir::new(&dest_path).into_iter().flatten() {
-                if entry
-                    .file_name()
-                    .to_string_lossy()
-                    .eq_ignore_ascii_case("VERSION")
-                {
-                    if let Ok(ver) = fs::read_to_string(entry.path()) {
-                        version = ver.trim().to_string();
-                        break;
-                    }
-                }
-            }
-        }
+        let metadata = detect_metadata(&dest_path);
+        let description = metadata
+            .get("description")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        let version = metadata
+            .get("version")
+            .and_then(|v| v.as_str())
+            .unwrap_or("0.0.1");
 
-        let proj_json = json!({
+        // A fresh clone starts at 0%; `template` is reserved for boilr templates
+        let mut proj_json = json!({
+            "schema_version": SCHEMA_VERSION,
             "name": project_name,
-            "template": template,
+            "template": null,
             "description": description,
             "version": version,
-            "completion": 1.0,
+            "completion": 0.0,
             "status": "active"
         });
+        if is_git_url {
+            proj_json["cloned_from"] = json!(source);
+        }
+
+        utils::write_json_atomic(&proj_file, &proj_json)?;
+        info!("📦 Generated default project.json for '{}'", project_name);
+    }
 
-        fs::write(&proj_file, serde_json::to_string_pretty(&proj_json)?)
-            .with_context(|| "Failed to write project.json")?;
-        println!("📦 Generated default project.json for '{}'", project_name);
+    // --- Apply a template over the clone ---
+    if let Some(t) = &args.template {
+        // Like create, a failed template leaves no half-made clone behind
+        if let Err(e) =
+            template::apply_boilr_template(t, &proj_file, &[], args.interactive, &template_dirs)
+        {
+            let _ = fs::remove_dir_all(&dest_path);
+            return Err(e);
+        }
+        let mut data = read_project_json(&proj_file)?;
+        data["template"] = json!(t);
+        utils::write_json_atomic(&proj_file, &data)?;
     }
 
     // --- Link in ~/projects if outside ---
-    if !dest_path.starts_with(projects_dir()) {
+    if !dest_path.starts_with(projects_dir()?) {
         link_in_projects_dir(&dest_path);
     }
 
-    println!(
+    info!(
         "✅ Project '{}' cloned successfully",
         dest_path.file_name().unwrap().to_string_lossy()
     );
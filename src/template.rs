@@ -1,16 +1,166 @@
+use crate::config;
+use crate::errors;
+use crate::output::info;
+use crate::utils;
+use anyhow::{Result, anyhow};
+use serde_json::{Value, json};
 use std::env;
 use std::fs;
 use std::io::{self, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
+/// Return boilr's own template registry (`~/.config/boilr/templates`)
+pub fn boilr_templates_dir() -> Option<PathBuf> {
+    let home_dir = env::var("HOME").ok()?;
+    Some(Path::new(&home_dir).join(".config/boilr/templates"))
+}
+
+/// All directories searched for templates, highest priority first:
+/// the `--template-dir` override, then `template_dirs` from the config,
+/// then boilr's registry
+pub fn template_dirs(override_dir: Option<&Path>) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = override_dir.map(Path::to_path_buf).into_iter().collect();
+    dirs.extend(config::load().template_dirs);
+    dirs.extend(boilr_templates_dir());
+    dirs
+}
+
+/// List the templates found in `dirs` as `(name, source dir)`, keeping the
+/// first occurrence of each name
+pub fn available_templates(dirs: &[PathBuf]) -> Vec<(String, PathBuf)> {
+    let mut templates: Vec<(String, PathBuf)> = Vec::new();
+
+    for dir in dirs {
+        let Ok(entries) = fs::read_dir(dir) else {
+            continue;
+        };
+
+        let mut names: Vec<String> = entries
+            .flatten()
+            .filter_map(|e| {
+                if e.path().is_dir() {
+                    e.file_name().to_str().map(|s| s.to_string())
+                } else {
+                    None
+                }
+            })
+            .collect();
+        names.sort();
+
+        for name in names {
+            if !templates.iter().any(|(t, _)| *t == name) {
+                templates.push((name, dir.clone()));
+            }
+        }
+    }
+
+    templates
+}
+
+/// Check that `template` exists in one of `dirs`, listing the valid ones if not
+pub fn validate_template(template: &str, dirs: &[PathBuf]) -> Result<()> {
+    let templates = available_templates(dirs);
+    if templates.iter().any(|(t, _)| t == template) {
+        return Ok(());
+    }
+
+    if templates.is_empty() {
+        return Err(errors::not_found(format!(
+            "Template '{}' not found: no templates are available",
+            template
+        )));
+    }
+
+    let list: Vec<String> = templates
+        .iter()
+        .map(|(t, source)| format!("  {} ({})", t, source.display()))
+        .collect();
+    Err(errors::not_found(format!(
+        "Template '{}' not found. Available templates:\n{}",
+        template,
+        list.join("\n")
+    )))
+}
+
+fn boilr_missing() -> anyhow::Error {
+    anyhow!(
+        "boilr is not installed or not on PATH. Install it (https://github.com/tmrts/boilr) or pass --no-template to skip templating"
+    )
+}
+
+/// Make sure the boilr binary can be run
+pub fn ensure_boilr() -> Result<()> {
+    match Command::new("boilr")
+        .arg("version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+    {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(boilr_missing()),
+        _ => Ok(()),
+    }
+}
+
 /// Apply a Boilr template
-pub fn apply_boilr_template(template: &str, json_path: &Path, interactive: bool) {
-    let json_data = fs::read_to_string(json_path).unwrap_or_else(|_| "{}".into());
+///
+/// project.json is handed to boilr as its data, with `extra` variables
+/// layered on top for this run only.
+pub fn apply_boilr_template(
+    template: &str,
+    json_path: &Path,
+    extra: &[(String, String)],
+    interactive: bool,
+    dirs: &[PathBuf],
+) -> Result<()> {
+    let mut data = utils::read_json(json_path).unwrap_or_else(|_| json!({}));
+    for (key, value) in extra {
+        data[key] = Value::String(value.clone());
+    }
+    let json_data = data.to_string();
     let boilr_path = "boilr";
 
+    // Templates outside boilr's registry have to be saved into it first.
+    // A throwaway name keeps a same-named registry template untouched
+    let mut saved_as = None;
+    if let Some((_, source)) = available_templates(dirs)
+        .into_iter()
+        .find(|(t, _)| t == template)
+        && Some(&source) != boilr_templates_dir().as_ref()
+    {
+        let temp_name = format!("project-{}-{}", template, std::process::id());
+        let saved = Command::new(boilr_path)
+            .arg("template")
+            .arg("save")
+            .arg(source.join(template))
+            .arg(&temp_name)
+            .status();
+        match saved {
+            Ok(status) if status.success() => saved_as = Some(temp_name),
+            Ok(status) => {
+                return Err(anyhow!(
+                    "boilr failed to save template '{}' from {} ({})",
+                    template,
+                    source.display(),
+                    status
+                ));
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Err(boilr_missing()),
+            Err(e) => return Err(anyhow!("Failed to run boilr: {}", e)),
+        }
+    }
+
+    // Render into the project that owns `.proj/project.json`
+    let project_dir = json_path
+        .parent()
+        .and_then(Path::parent)
+        .unwrap_or(Path::new("."));
     let mut cmd = Command::new(boilr_path);
-    cmd.arg("template").arg("use").arg(template).arg(".");
+    cmd.arg("template")
+        .arg("use")
+        .arg(saved_as.as_deref().unwrap_or(template))
+        .arg(".")
+        .current_dir(project_dir);
 
     if !interactive {
         cmd.arg("--use-defaults");
@@ -21,40 +171,49 @@ pub fn apply_boilr_template(template: &str, json_path: &Path, interactive: bool)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit());
 
-    println!("⚙️ Applying boilr template: {}", template);
+    info!("⚙️ Applying boilr template: {}", template);
+
+    let result = cmd.status();
+
+    if let Some(temp_name) = &saved_as {
+        let _ = Command::new(boilr_path)
+            .arg("template")
+            .arg("delete")
+            .arg(temp_name)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
 
-    let status = cmd.status().expect("Failed to run boilr");
+    let status = match result {
+        Ok(status) => status,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Err(boilr_missing()),
+        Err(e) => return Err(anyhow!("Failed to run boilr: {}", e)),
+    };
 
     if !status.success() {
-        eprintln!("❌ Boilr failed with exit code {}", status);
+        return Err(anyhow!(
+            "boilr failed to apply template '{}' ({})",
+            template,
+            status
+        ));
     }
+    Ok(())
 }
 
 /// Ask the user to choose a template interactively
-pub fn select_template() -> Option<String> {
-    let home_dir = env::var("HOME").ok()?;
-    let templates_dir = Path::new(&home_dir).join(".config/boilr/templates");
-    let entries = fs::read_dir(&templates_dir).ok()?;
-
-    let templates: Vec<String> = entries
-        .flatten()
-        .filter_map(|e| {
-            if e.path().is_dir() {
-                e.file_name().to_str().map(|s| s.to_string())
-            } else {
-                None
-            }
-        })
-        .collect();
+pub fn select_template(dirs: &[PathBuf]) -> Option<String> {
+    let templates = available_templates(dirs);
 
     if templates.is_empty() {
-        eprintln!("No templates found in {}", templates_dir.display());
+        let searched: Vec<String> = dirs.iter().map(|d| d.display().to_string()).collect();
+        eprintln!("No templates found in {}", searched.join(", "));
         return None;
     }
 
     println!("Available templates:");
-    for (i, t) in templates.iter().enumerate() {
-        println!("  {}. {}", i + 1, t);
+    for (i, (t, source)) in templates.iter().enumerate() {
+        println!("  {}. {} ({})", i + 1, t, source.display());
     }
 
     print!("Select template: ");
@@ -64,14 +223,16 @@ pub fn select_template() -> Option<String> {
     io::stdin().read_line(&mut input).ok()?;
     let trimmed = input.trim();
 
-    if let Ok(index) = trimmed.parse::<usize>() {
-        if index > 0 && index <= templates.len() {
-            return Some(templates[index - 1].clone());
-        }
+    if let Ok(index) = trimmed.parse::<usize>()
+        && index > 0
+        && index <= templates.len()
+    {
+        return Some(templates[index - 1].0.clone());
     }
 
     templates
         .iter()
+        .map(|(t, _)| t)
         .find(|t| t.eq_ignore_ascii_case(trimmed))
         .cloned()
 }
@@ -1,56 +1,57 @@
-
-
+use crate::output::info;
+use crate::project;
+use crate::utils;
 use anyhow::Result;
-use std::fs;
-use serde_json::{Value, json};
-use std::path::{Path};
-use std::fs::File;
-use std::io::Write;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 
-
+#[allow(dead_code)]
 #[derive(Serialize, Deserialize)]
 pub struct Todo {
     title: String,
     description: String,
-    complete: bool
+    complete: bool,
 }
 
-
-pub fn read_json(path: &Path) -> Value {
-    if let Ok(content) = fs::read_to_string(path) {
-        serde_json::from_str(&content).unwrap_or(json!({}))
-    } else {
-        json!({})
-    }
+/// The enclosing project's `.proj/todos.json`, shared by every todo command
+/// and the counts in list and status
+fn todos_file() -> Result<PathBuf> {
+    Ok(project::require_enclosing_project()?.join(".proj/todos.json"))
 }
 
 pub fn todo_list() -> Result<()> {
-    let project_config = dirs::home_dir().unwrap().join(".config/project/");
-    let todos_file = project_config.join("todos.json");
-    let proj_file = Path::new(&todos_file);
-    
-    if !project_config.exists(){
-        fs::create_dir_all(&project_config)?;
+    let todos_file = todos_file()?;
+    if !todos_file.is_file() {
+        info!("No todos");
+        return Ok(());
     }
 
-    let todos_file = project_config.join("todos.json");
-    if !todos_file.exists(){
-        let mut f = File::create(todos_file)?;
-        f.write_all(b"{\"todos\":[\"Configure Project Todos\"]}")?;
-
-
+    let data = utils::read_json(&todos_file)?;
+    let todos = data["todos"].as_array().cloned().unwrap_or_default();
+    if todos.is_empty() {
+        info!("No todos");
+        return Ok(());
     }
-    
-    if let Ok(content) = fs::read_to_string(proj_file) {
-        println!("{}", content)
-    } else {
-        println!("Fuck")
-    }
-    
 
-
-    println!("List todos");
+    // Entries are either plain strings or `Todo` objects
+    for (i, todo) in todos.iter().enumerate() {
+        let (title, complete) = match todo {
+            Value::String(title) => (title.as_str(), false),
+            _ => (
+                todo["title"].as_str().unwrap_or_default(),
+                todo["complete"].as_bool().unwrap_or(false),
+            ),
+        };
+        let mark = if complete { "x" } else { " " };
+        println!("{:>2}. [{}] {}", i + 1, mark, title);
+        if let Some(description) = todo["description"].as_str()
+            && !description.is_empty()
+        {
+            println!("       {}", description);
+        }
+    }
     Ok(())
 }
 
@@ -62,4 +63,79 @@ pub fn todo_add(text: &str) -> Result<()> {
 pub fn todo_remove(pattern: &str) -> Result<()> {
     println!("Remove todo: {}", pattern);
     Ok(())
-} 
+}
+
+/// Drop completed todos, or all of them with `all`
+///
+/// Clearing everything asks first when stdin is a terminal.
+pub fn todo_clear(all: bool) -> Result<()> {
+    let todos_file = todos_file()?;
+    if !todos_file.is_file() {
+        info!("No todos to clear");
+        return Ok(());
+    }
+    let mut data = utils::read_json(&todos_file)?;
+    let todos = data["todos"].as_array().cloned().unwrap_or_default();
+
+    if all && !todos.is_empty() && io::stdin().is_terminal() {
+        print!("⚠️  Remove all {} todos? [y/N]: ", todos.len());
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let answer = input.trim().to_lowercase();
+        if answer != "y" && answer != "yes" {
+            println!("❎ Aborted");
+            return Ok(());
+        }
+    }
+
+    let kept: Vec<Value> = if all {
+        Vec::new()
+    } else {
+        todos
+            .iter()
+            .filter(|todo| !todo["complete"].as_bool().unwrap_or(false))
+            .cloned()
+            .collect()
+    };
+
+    let removed = todos.len() - kept.len();
+    if removed == 0 {
+        info!("No todos to clear");
+        return Ok(());
+    }
+
+    data["todos"] = Value::Array(kept);
+    utils::write_json_atomic(&todos_file, &data)?;
+    info!(
+        "🧹 Cleared {} todo{}",
+        removed,
+        if removed == 1 { "" } else { "s" }
+    );
+    Ok(())
+}
+
+/// Number of unfinished todos in a project's `.proj/todos.json`, or `None`
+/// when the project has no todo file
+pub fn open_count(project_path: &Path) -> Option<usize> {
+    let todos_file = project_path.join(".proj/todos.json");
+    if !todos_file.is_file() {
+        return None;
+    }
+    let data = utils::read_json(&todos_file).ok()?;
+    let todos = data["todos"].as_array()?;
+    Some(
+        todos
+            .iter()
+            .filter(|todo| !todo["complete"].as_bool().unwrap_or(false))
+            .count(),
+    )
+}
+
+/// ` · N todos` for list and status lines, empty without a todo file
+pub fn count_note(project_path: &Path) -> String {
+    match open_count(project_path) {
+        Some(count) => format!(" · {} todo{}", count, if count == 1 { "" } else { "s" }),
+        None => String::new(),
+    }
+}
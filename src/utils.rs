@@ -1,11 +1,175 @@
+use anyhow::{Context, Result, anyhow};
 use serde_json::{Value, json};
 use std::fs;
-use std::path::Path;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 
-pub fn read_json(path: &Path) -> Value {
-    if let Ok(content) = fs::read_to_string(path) {
-        serde_json::from_str(&content).unwrap_or(json!({}))
+/// The user's home directory, or an error explaining how to do without one
+pub fn home_dir() -> Result<PathBuf> {
+    dirs::home_dir().ok_or_else(|| {
+        anyhow!("Could not determine home directory; set HOME or configure projects_dir")
+    })
+}
+
+/// Read a JSON file, treating a missing file as an empty object
+///
+/// A file that exists but can't be read or parsed is an error, so callers
+/// never overwrite data they failed to load.
+pub fn read_json(path: &Path) -> Result<Value> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(json!({})),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+
+    serde_json::from_str(&content).with_context(|| format!("Invalid JSON in {}", path.display()))
+}
+
+/// Write `data` as pretty JSON by writing a temp file next to `path` and
+/// renaming it over the target, so an interrupted write never truncates it
+pub fn write_json_atomic(path: &Path, data: &Value) -> Result<()> {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp_path = path.with_file_name(format!(".{}.tmp", file_name));
+
+    let write = || -> io::Result<()> {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(serde_json::to_string_pretty(data)?.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)
+    };
+
+    write()
+        .inspect_err(|_| {
+            let _ = fs::remove_file(&tmp_path);
+        })
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Format a byte count as a human-readable size, e.g. `1.5 MB`
+pub fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Match `name` against a shell-style glob where `*` is any run of characters
+/// and `?` is any single character
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let mut re = String::from("^");
+    for c in pattern.chars() {
+        match c {
+            '*' => re.push_str(".*"),
+            '?' => re.push('.'),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push('$');
+
+    regex::Regex::new(&re).is_ok_and(|re| re.is_match(name))
+}
+
+/// Ask for a value on stdin, showing `default` in brackets and returning it
+/// when the answer is empty
+pub fn prompt(label: &str, default: &str) -> io::Result<String> {
+    print!("{} [{}]: ", label, default);
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let answer = input.trim();
+    Ok(if answer.is_empty() { default } else { answer }.to_string())
+}
+
+/// Parse a CLI value as a bool, integer or float, falling back to a string
+pub fn parse_value(value: &str) -> Value {
+    if let Ok(b) = value.parse::<bool>() {
+        Value::Bool(b)
+    } else if let Ok(i) = value.parse::<i64>() {
+        json!(i)
+    } else if let Ok(f) = value.parse::<f64>()
+        && f.is_finite()
+    {
+        json!(f)
     } else {
-        json!({})
+        Value::String(value.to_string())
+    }
+}
+
+/// Look up a dot-separated key such as `build.target` in a JSON object
+pub fn get_path<'a>(data: &'a Value, key: &str) -> Option<&'a Value> {
+    key.split('.')
+        .try_fold(data, |current, part| current.get(part))
+}
+
+/// Set a dot-separated key, creating missing intermediate objects
+///
+/// An existing intermediate that isn't an object is an error rather than
+/// being overwritten, so `version.major=1` can't clobber `"version": "0.1.0"`.
+pub fn set_path(data: &mut Value, key: &str, value: Value) -> Result<()> {
+    let mut parts = key.split('.').peekable();
+    let mut walked: Vec<&str> = Vec::new();
+    let mut current = data;
+
+    while let Some(part) = parts.next() {
+        let Some(map) = current.as_object_mut() else {
+            return Err(if walked.is_empty() {
+                anyhow!("Cannot set `{}`: the document is not an object", key)
+            } else {
+                anyhow!("`{}` is not an object", walked.join("."))
+            });
+        };
+        if parts.peek().is_none() {
+            map.insert(part.to_string(), value);
+            return Ok(());
+        }
+
+        let next = map.entry(part).or_insert_with(|| json!({}));
+        if next.is_null() {
+            *next = json!({});
+        }
+        walked.push(part);
+        current = next;
+    }
+    Ok(())
+}
+
+/// Remove a dot-separated key, returning the removed value if it existed
+pub fn remove_path(data: &mut Value, key: &str) -> Option<Value> {
+    let (parent, last) = match key.rsplit_once('.') {
+        Some((parent, last)) => (
+            parent
+                .split('.')
+                .try_fold(data, |current, part| current.get_mut(part))?,
+            last,
+        ),
+        None => (data, key),
+    };
+    parent.as_object_mut()?.remove(last)
+}
+
+/// Move a directory, copying then deleting when `rename` can't cross filesystems
+pub fn move_dir(src: &Path, dest: &Path) -> anyhow::Result<()> {
+    match fs::rename(src, dest) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {
+            fs_extra::dir::copy(
+                src,
+                dest,
+                &fs_extra::dir::CopyOptions::new().copy_inside(true),
+            )?;
+            fs::remove_dir_all(src)?;
+            Ok(())
+        }
+        Err(e) => Err(e.into()),
     }
 }
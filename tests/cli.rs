@@ -0,0 +1,577 @@
+//! End-to-end tests that run the `project` binary against a throwaway HOME
+//! and `PROJECT_HOME`, so nothing outside a temp dir is touched.
+
+use serde_json::Value;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use tempfile::TempDir;
+
+/// A temp dir holding a fake home, a projects root and a working directory
+struct Sandbox {
+    _dir: TempDir,
+    home: PathBuf,
+    projects: PathBuf,
+    work: PathBuf,
+}
+
+impl Sandbox {
+    fn new() -> Sandbox {
+        let dir = TempDir::new().unwrap();
+        let root = fs::canonicalize(dir.path()).unwrap();
+        let sandbox = Sandbox {
+            home: root.join("home"),
+            projects: root.join("projects"),
+            work: root.join("work"),
+            _dir: dir,
+        };
+        for d in [&sandbox.home, &sandbox.projects, &sandbox.work] {
+            fs::create_dir_all(d).unwrap();
+        }
+        sandbox
+    }
+
+    fn archives(&self) -> PathBuf {
+        self.projects.join(".archives")
+    }
+
+    /// Directory searched first on PATH, for stand-ins of external tools
+    fn bin(&self) -> PathBuf {
+        self.home.join("bin")
+    }
+
+    /// Put an executable shell script called `name` on the sandbox PATH
+    #[cfg(unix)]
+    fn fake_tool(&self, name: &str, script: &str) {
+        use std::os::unix::fs::PermissionsExt;
+
+        fs::create_dir_all(self.bin()).unwrap();
+        let path = self.bin().join(name);
+        fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    /// Run the binary in `cwd` with stdin closed
+    fn run_in(&self, cwd: &Path, args: &[&str]) -> Output {
+        let mut path = vec![self.bin()];
+        path.extend(std::env::split_paths(
+            &std::env::var_os("PATH").unwrap_or_default(),
+        ));
+
+        Command::new(env!("CARGO_BIN_EXE_project"))
+            .args(args)
+            .current_dir(cwd)
+            .env("HOME", &self.home)
+            .env("PROJECT_HOME", &self.projects)
+            .env("PATH", std::env::join_paths(path).unwrap())
+            .env_remove("VISUAL")
+            .env_remove("EDITOR")
+            .env("GIT_AUTHOR_NAME", "Test")
+            .env("GIT_AUTHOR_EMAIL", "test@example.com")
+            .env("GIT_COMMITTER_NAME", "Test")
+            .env("GIT_COMMITTER_EMAIL", "test@example.com")
+            .stdin(Stdio::null())
+            .output()
+            .unwrap()
+    }
+
+    fn run(&self, args: &[&str]) -> Output {
+        self.run_in(&self.work, args)
+    }
+
+    /// Run and assert success, returning stdout
+    fn ok_in(&self, cwd: &Path, args: &[&str]) -> String {
+        let out = self.run_in(cwd, args);
+        assert!(
+            out.status.success(),
+            "`project {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&out.stderr)
+        );
+        String::from_utf8(out.stdout).unwrap()
+    }
+
+    fn ok(&self, args: &[&str]) -> String {
+        self.ok_in(&self.work, args)
+    }
+
+    /// Names of the archives written so far
+    fn archive_files(&self) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(self.archives())
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.file_name().to_string_lossy().into_owned())
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+}
+
+fn read_project_json(project: &Path) -> Value {
+    let content = fs::read_to_string(project.join(".proj/project.json")).unwrap();
+    serde_json::from_str(&content).unwrap()
+}
+
+/// Deterministic, poorly compressible bytes
+fn noise(len: usize) -> Vec<u8> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+#[test]
+fn create_set_list_archive_restore_remove() {
+    let sb = Sandbox::new();
+    let demo = sb.projects.join("demo");
+
+    // create
+    sb.ok(&["create", "demo", "--no-template", "--no-upstream"]);
+    assert!(demo.is_dir());
+    let data = read_project_json(&demo);
+    assert_eq!(data["name"], "demo");
+    assert_eq!(data["status"], "active");
+    assert_eq!(data["completion"], 0.0);
+
+    // set
+    sb.ok_in(
+        &demo,
+        &["set", "completion=0.5", "status=paused", "build.target=x86"],
+    );
+    let data = read_project_json(&demo);
+    assert_eq!(data["completion"], 0.5);
+    assert_eq!(data["status"], "paused");
+    assert_eq!(data["build"]["target"], "x86");
+
+    // list
+    let listed = sb.ok(&["list", "--path", "-s", "paused"]);
+    assert_eq!(listed.lines().collect::<Vec<_>>(), [demo.to_str().unwrap()]);
+    assert!(sb.ok(&["list", "--path"]).trim().is_empty());
+
+    // archive
+    fs::write(demo.join("notes.txt"), "keep me").unwrap();
+    sb.ok(&["archive", "demo"]);
+    assert!(!demo.exists());
+    let archives = sb.archive_files();
+    assert_eq!(archives.len(), 1);
+    assert!(archives[0].starts_with("demo_") && archives[0].ends_with(".zip"));
+
+    // restore
+    sb.ok(&["restore", "demo", "--latest"]);
+    assert_eq!(
+        fs::read_to_string(demo.join("notes.txt")).unwrap(),
+        "keep me"
+    );
+    let data = read_project_json(&demo);
+    assert_eq!(data["completion"], 0.5);
+    assert_eq!(data["status"], "paused");
+
+    // remove
+    sb.ok(&["remove", "demo", "--force"]);
+    assert!(!demo.exists());
+    let trashed: Vec<_> = fs::read_dir(sb.home.join(".proj/trash"))
+        .unwrap()
+        .flatten()
+        .map(|e| e.path())
+        .collect();
+    assert_eq!(trashed.len(), 1);
+    assert_eq!(read_project_json(&trashed[0])["name"], "demo");
+}
+
+#[test]
+fn large_files_round_trip_through_zip_and_tgz() {
+    let sb = Sandbox::new();
+    let big = noise(6 * 1024 * 1024);
+
+    for format in ["zip", "tgz"] {
+        let name = format!("big-{}", format);
+        let project = sb.projects.join(&name);
+        sb.ok(&["create", &name, "--no-template", "--no-upstream"]);
+        fs::create_dir_all(project.join("data")).unwrap();
+        fs::write(project.join("data/blob.bin"), &big).unwrap();
+
+        sb.ok(&["archive", &name, "--format", format]);
+        assert!(!project.exists());
+
+        sb.ok(&["restore", &name, "--latest"]);
+        let restored = fs::read(project.join("data/blob.bin")).unwrap();
+        assert!(restored == big, "{} round trip changed the file", format);
+    }
+}
+
+#[test]
+fn restore_rejects_entries_outside_the_destination() {
+    let sb = Sandbox::new();
+    fs::create_dir_all(sb.archives()).unwrap();
+
+    let archive = sb.archives().join("evil_20260101_000000.zip");
+    let mut zip = zip::ZipWriter::new(fs::File::create(&archive).unwrap());
+    let options = zip::write::SimpleFileOptions::default();
+    zip.start_file(".proj/project.json", options).unwrap();
+    zip.write_all(br#"{"name": "evil"}"#).unwrap();
+    zip.start_file("../evil.txt", options).unwrap();
+    zip.write_all(b"escaped").unwrap();
+    zip.finish().unwrap();
+
+    let out = sb.run(&["restore", "evil_20260101_000000"]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("would be extracted outside"));
+    assert!(!sb.projects.join("evil.txt").exists());
+}
+
+#[test]
+fn create_places_bare_names_in_projects_and_paths_in_place() {
+    let sb = Sandbox::new();
+
+    // A bare name goes straight into the projects root
+    sb.ok(&["create", "alpha", "--no-template", "--no-upstream"]);
+    let alpha = sb.projects.join("alpha");
+    assert!(alpha.is_dir() && !alpha.is_symlink());
+    assert!(!sb.work.join("alpha").exists());
+
+    // A path is created where it says and linked into the projects root
+    sb.ok(&["create", "sub/beta", "--no-template", "--no-upstream"]);
+    let beta = sb.work.join("sub/beta");
+    assert!(beta.join(".proj/project.json").is_file());
+    let link = sb.projects.join("beta");
+    assert!(link.is_symlink());
+    assert_eq!(fs::read_link(&link).unwrap(), beta);
+
+    // --here creates a bare name in the current directory
+    sb.ok(&[
+        "create",
+        "gamma",
+        "--here",
+        "--no-template",
+        "--no-upstream",
+    ]);
+    assert!(sb.work.join("gamma/.proj/project.json").is_file());
+    assert!(sb.projects.join("gamma").is_symlink());
+
+    // Taken names are refused with the conflict exit code
+    let out = sb.run(&["create", "alpha", "--no-template", "--no-upstream"]);
+    assert_eq!(out.status.code(), Some(3));
+}
+
+#[test]
+fn projects_reachable_from_cwd_and_projects_root_are_listed_once() {
+    let sb = Sandbox::new();
+    sb.ok(&["create", "sub/beta", "--no-template", "--no-upstream"]);
+
+    // beta is found under cwd and again through its symlink in the projects root
+    let listed = sb.ok(&["list", "--path", "-s", "all"]);
+    let beta = sb.work.join("sub/beta");
+    assert_eq!(listed.lines().collect::<Vec<_>>(), [beta.to_str().unwrap()]);
+
+    let scanned = sb.ok(&["scan", "--recursive"]);
+    assert_eq!(scanned.matches("Found project: beta").count(), 1);
+
+    let lines: Vec<Value> = sb
+        .ok(&["scan", "--recursive", "--json"])
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0]["name"], "beta");
+    assert_eq!(lines[0]["path"], beta.to_str().unwrap());
+}
+
+#[test]
+fn unset_removes_top_level_and_nested_keys() {
+    let sb = Sandbox::new();
+    let demo = sb.projects.join("demo");
+    sb.ok(&["create", "demo", "--no-template", "--no-upstream"]);
+    sb.ok_in(
+        &demo,
+        &["set", "owner=me", "build.target=x86", "build.opt=3"],
+    );
+
+    let out = sb.run_in(&demo, &["unset", "owner", "build.target", "missing"]);
+    assert!(out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("'missing' not found"));
+
+    let data = read_project_json(&demo);
+    assert!(data.get("owner").is_none());
+    assert!(data["build"].get("target").is_none());
+    assert_eq!(data["build"]["opt"], 3);
+
+    assert_eq!(sb.ok_in(&demo, &["get", "name"]), "demo\n");
+    assert_eq!(sb.ok_in(&demo, &["get", "build.opt"]), "3\n");
+    assert_eq!(sb.run_in(&demo, &["get", "owner"]).status.code(), Some(2));
+    assert_eq!(
+        sb.ok_in(&demo, &["get", "owner", "--default", "nobody"]),
+        "nobody\n"
+    );
+}
+
+#[test]
+fn progress_bars_survive_completion_above_one() {
+    let sb = Sandbox::new();
+    let demo = sb.projects.join("demo");
+    sb.ok(&["create", "demo", "--no-template", "--no-upstream"]);
+    sb.ok(&["create", "other", "--no-template", "--no-upstream"]);
+    sb.ok(&["set-status", "paused", "other"]);
+
+    for (completion, line) in [
+        ("1.0", "demo + [██████████] 100%"),
+        ("1.5", "demo + [██████████] 150%"),
+        ("-0.2", "demo + [░░░░░░░░░░] -20%"),
+    ] {
+        sb.ok_in(&demo, &["set", &format!("completion={}", completion)]);
+        let listed = sb.ok(&["list", "-p", "--bar-width", "10"]);
+        assert_eq!(listed.lines().collect::<Vec<_>>(), [line]);
+    }
+}
+
+#[test]
+fn init_in_an_existing_project_only_applies_vars() {
+    let sb = Sandbox::new();
+    let demo = sb.work.join("demo");
+    fs::create_dir_all(&demo).unwrap();
+    sb.ok_in(&demo, &["init", "--no-template", "--no-upstream"]);
+    fs::remove_dir_all(demo.join(".git")).unwrap();
+
+    sb.ok_in(&demo, &["init", "owner=me", "--no-template", "--no-upstream"]);
+    assert_eq!(read_project_json(&demo)["owner"], "me");
+    assert!(!demo.join(".git").exists());
+
+    sb.ok_in(&demo, &["init", "--reinit", "--no-template", "--no-upstream"]);
+    assert!(demo.join(".git").is_dir());
+}
+
+#[test]
+fn set_refuses_to_nest_under_a_scalar() {
+    let sb = Sandbox::new();
+    let demo = sb.projects.join("demo");
+    sb.ok(&["create", "demo", "--no-template", "--no-upstream"]);
+
+    let out = sb.run_in(&demo, &["set", "version.major=1"]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("`version` is not an object"));
+    assert_eq!(read_project_json(&demo)["version"], "0.1.0");
+}
+
+#[cfg(unix)]
+#[test]
+fn create_and_init_clean_up_when_the_template_fails() {
+    let sb = Sandbox::new();
+    sb.fake_tool("boilr", r#"[ "$2" = use ] && exit 1; exit 0"#);
+    let templates = sb.home.join("templates");
+    fs::create_dir_all(templates.join("broken")).unwrap();
+
+    let out = sb.run(&[
+        "create",
+        "demo",
+        "--template",
+        "broken",
+        "--template-dir",
+        templates.to_str().unwrap(),
+        "--no-upstream",
+    ]);
+    assert_eq!(out.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&out.stderr).contains("failed to apply template 'broken'"));
+    assert!(!sb.projects.join("demo").exists());
+
+    // An existing directory keeps its files and gains no .proj or .git
+    let legacy = sb.work.join("legacy");
+    fs::create_dir_all(&legacy).unwrap();
+    fs::write(legacy.join("README.md"), "# legacy\n").unwrap();
+    let out = sb.run_in(
+        &legacy,
+        &[
+            "init",
+            "--template",
+            "broken",
+            "--template-dir",
+            templates.to_str().unwrap(),
+            "--no-upstream",
+        ],
+    );
+    assert_eq!(out.status.code(), Some(1));
+    assert!(legacy.join("README.md").is_file());
+    assert!(!legacy.join(".proj").exists());
+    assert!(!legacy.join(".git").exists());
+}
+
+#[test]
+fn clone_cleans_up_when_the_template_fails() {
+    let sb = Sandbox::new();
+    sb.ok(&["create", "demo", "--no-template", "--no-upstream"]);
+    sb.fake_tool("boilr", r#"[ "$2" = use ] && exit 1; exit 0"#);
+    fs::create_dir_all(sb.home.join(".config/boilr/templates/broken")).unwrap();
+
+    let out = sb.run(&["clone", "demo", "copy", "--template", "broken"]);
+    assert_eq!(out.status.code(), Some(1));
+    assert!(!sb.work.join("copy").exists());
+    assert!(!sb.projects.join("copy").exists());
+}
+
+#[test]
+fn archive_all_removes_the_projects_link() {
+    let sb = Sandbox::new();
+    sb.ok(&["create", "sub/beta", "--no-template", "--no-upstream"]);
+    sb.ok(&["set-status", "done", "beta"]);
+
+    sb.ok(&["archive-all", "--status", "done"]);
+    assert!(!sb.work.join("sub/beta").exists());
+    assert!(!sb.projects.join("beta").is_symlink());
+    assert_eq!(sb.archive_files().len(), 1);
+}
+
+#[test]
+fn archive_deletes_a_linked_project_and_its_link() {
+    let sb = Sandbox::new();
+    sb.ok(&["create", "sub/beta", "--no-template", "--no-upstream"]);
+    let beta = sb.work.join("sub/beta");
+
+    let planned = sb.ok(&["archive", "beta", "--dry-run"]);
+    assert!(
+        planned.contains(&format!("Would delete '{}'", beta.display())),
+        "{}",
+        planned
+    );
+    assert!(beta.exists());
+
+    sb.ok(&["archive", "beta"]);
+    assert!(!beta.exists());
+    assert!(!sb.projects.join("beta").is_symlink());
+    assert_eq!(sb.archive_files().len(), 1);
+}
+
+#[test]
+fn forced_restore_replaces_a_linked_project_in_place() {
+    let sb = Sandbox::new();
+    sb.ok(&["create", "sub/beta", "--no-template", "--no-upstream"]);
+    let beta = sb.work.join("sub/beta");
+    fs::write(beta.join("notes.txt"), "archived").unwrap();
+    sb.ok(&["archive", "beta", "--keep"]);
+    fs::write(beta.join("notes.txt"), "edited").unwrap();
+
+    sb.ok(&["restore", "beta", "--latest", "--force"]);
+    assert_eq!(fs::read_to_string(beta.join("notes.txt")).unwrap(), "archived");
+    assert_eq!(fs::read_link(sb.projects.join("beta")).unwrap(), beta);
+}
+
+#[test]
+fn run_forwards_args_through_sh_whatever_the_shell() {
+    let sb = Sandbox::new();
+    let demo = sb.projects.join("demo");
+    sb.ok(&["create", "demo", "--no-template", "--no-upstream"]);
+    sb.ok_in(&demo, &["set", r#"scripts.show=printf "[%s]""#]);
+
+    let out = Command::new(env!("CARGO_BIN_EXE_project"))
+        .args(["run", "show", "a b", "-c"])
+        .current_dir(&demo)
+        .env("HOME", &sb.home)
+        .env("PROJECT_HOME", &sb.projects)
+        .env("SHELL", "/nonexistent/fish")
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    assert!(String::from_utf8_lossy(&out.stdout).ends_with("[a b][-c]"));
+}
+
+#[test]
+fn migrate_undo_works_from_anywhere_and_copy_clears_it() {
+    let sb = Sandbox::new();
+    sb.ok(&["create", "sub/beta", "--no-template", "--no-upstream"]);
+    let beta = sb.work.join("sub/beta");
+    let moved = sb.work.parent().unwrap().join("elsewhere/beta");
+
+    // A relative destination, on a home without ~/.proj yet
+    sb.ok(&["migrate", "beta", "-d", "../elsewhere"]);
+    assert!(moved.join(".proj/project.json").is_file());
+    assert_eq!(fs::read_link(sb.projects.join("beta")).unwrap(), moved);
+
+    sb.ok_in(&sb.projects, &["migrate", "--undo"]);
+    assert!(beta.join(".proj/project.json").is_file());
+    assert!(!moved.exists());
+    assert_eq!(fs::read_link(sb.projects.join("beta")).unwrap(), beta);
+
+    // A project that lived in ~/projects gets its directory back in place of the link
+    sb.ok(&["create", "demo", "--no-template", "--no-upstream"]);
+    let demo = sb.projects.join("demo");
+    sb.ok(&["migrate", "demo", "-d", "../elsewhere"]);
+    assert!(demo.is_symlink());
+    sb.ok(&["migrate", "--undo"]);
+    assert!(!demo.is_symlink());
+    assert!(demo.join(".proj/project.json").is_file());
+
+    // A move followed by a copy leaves nothing to undo
+    sb.ok(&["migrate", "beta", "-d", "../elsewhere"]);
+    sb.ok(&["migrate", "beta", "-d", "../copies", "--copy"]);
+    let out = sb.run(&["migrate", "--undo"]);
+    assert_eq!(out.status.code(), Some(2));
+    assert!(moved.exists());
+}
+
+#[test]
+fn piped_list_output_has_no_color_codes() {
+    let sb = Sandbox::new();
+    sb.ok(&["create", "demo", "--no-template", "--no-upstream"]);
+
+    let listed = sb.ok(&["list", "-p", "--bar-width", "10"]);
+    assert!(listed.starts_with("demo "), "{}", listed);
+    assert!(!listed.contains('\x1b'), "{:?}", listed);
+}
+
+#[test]
+fn which_prints_the_real_path_or_exits_one() {
+    let sb = Sandbox::new();
+    sb.ok(&["create", "sub/beta", "--no-template", "--no-upstream"]);
+
+    let beta = sb.work.join("sub/beta");
+    assert_eq!(sb.ok(&["which", "beta"]), format!("{}\n", beta.display()));
+    assert_eq!(sb.run(&["which", "missing"]).status.code(), Some(1));
+}
+
+#[test]
+fn list_dirty_sees_edits_below_the_top_level() {
+    let sb = Sandbox::new();
+    sb.ok(&["create", "demo", "--no-template", "--no-upstream"]);
+    let demo = sb.projects.join("demo");
+    fs::create_dir(demo.join("src")).unwrap();
+    fs::write(demo.join("src/main.rs"), "fn main() {}\n").unwrap();
+    for args in [&["add", "-A"][..], &["commit", "-qm", "init"]] {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(&demo)
+            .env("GIT_AUTHOR_NAME", "Test")
+            .env("GIT_AUTHOR_EMAIL", "test@example.com")
+            .env("GIT_COMMITTER_NAME", "Test")
+            .env("GIT_COMMITTER_EMAIL", "test@example.com")
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+    assert!(!sb.ok(&["list", "--dirty"]).contains("demo"));
+
+    // Leaves every top-level mtime alone
+    fs::write(demo.join("src/main.rs"), "fn main() { todo!() }\n").unwrap();
+    assert!(sb.ok(&["list", "--dirty"]).contains("demo"));
+}
+
+#[test]
+fn todo_clear_prunes_the_file_list_counts_from() {
+    let sb = Sandbox::new();
+    let demo = sb.projects.join("demo");
+    sb.ok(&["create", "demo", "--no-template", "--no-upstream"]);
+    fs::write(
+        demo.join(".proj/todos.json"),
+        r#"{"todos": [{"title": "ship", "complete": true}, {"title": "test", "complete": false}]}"#,
+    )
+    .unwrap();
+
+    sb.ok_in(&demo, &["todo", "clear"]);
+    assert_eq!(sb.ok_in(&demo, &["todo", "list"]), " 1. [ ] test\n");
+    assert!(sb.ok(&["list"]).contains("· 1 todo"));
+}